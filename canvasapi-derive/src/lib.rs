@@ -0,0 +1,576 @@
+//! Procedural macros for [canvasapi](https://crates.io/crates/canvasapi).
+//!
+//! The macros are re-exported by `canvasapi`, and should be used from there.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Expr, Fields, Ident, ItemStruct, LitStr, Token, Type};
+
+/// Declare an endpoint of the Canvas API on a struct with the parameters of the request.
+///
+/// ```ignore
+/// #[canvas_endpoint(GET, "courses/{course_id}/assignments", paged, output = Assignment)]
+/// pub struct GetAssignments {
+///     pub course_id: usize,
+///     #[query(name = "include[]")]
+///     pub include: Vec<String>,
+///     pub search_term: Option<String>,
+/// }
+///
+/// #[canvas_endpoint(PUT, "courses/{course_id}/assignments/{id}", output = Assignment)]
+/// pub struct EditAssignment {
+///     pub course_id: usize,
+///     pub id: usize,
+///     #[body]
+///     pub assignment: serde_json::Value,
+/// }
+/// ```
+///
+/// The attribute takes the method, the path relative to `/api/v1/`, and:
+///
+/// - `output = Type`: the type of the response, or of a single object for paged endpoints.
+/// - `paged`: the endpoint returns a list of objects over one or more pages.
+/// - `key = "name"`: the response is a map, of which the value with this key is parsed as
+///   the output.
+/// - `parameter = expr`: a parameter that is always sent, for example
+///   `parameter = EnrollmentType::Student`. It can be given more than once.
+///
+/// The fields between braces in the path are formatted into the path, with the characters that
+/// are not allowed in a path percent-encoded. The other fields are sent as query parameters: an
+/// `Option` only when it is `Some`, and a `Vec` once for every value, with `[]` appended to the
+/// name. The name can be changed with `#[query(name = "...")]`. The values are formatted with
+/// `Display`.
+///
+/// The fields with the `#[body]` attribute are sent in the JSON body instead, as a member of an
+/// object with the name of the field, serialized with `Serialize`. An `Option` is only sent when it
+/// is `Some`. A body can not be sent with a `GET` request.
+///
+/// The `Endpoint` trait is implemented for the struct, together with a `query` method that
+/// creates the request, and `fetch` and `fetch_blocking` methods that send it.
+///
+/// For the query parameters, a trait named after the struct, for example
+/// `GetAssignmentsParameters`, is generated with a setter for every parameter. It is implemented
+/// for the struct and for its request, such that only the parameters of the endpoint can be set:
+/// `course.get_assignments()?.include(AssignmentInclude::Submission)`. The setter of a `Vec`
+/// adds a value, and can be called more than once.
+#[proc_macro_attribute]
+pub fn canvas_endpoint(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as Args);
+    let item = parse_macro_input!(input as ItemStruct);
+
+    expand(args, item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The arguments of the attribute, for example `GET, "courses/{course_id}", output = Course`.
+struct Args {
+    method: Ident,
+    path: LitStr,
+    paged: bool,
+    output: Option<Type>,
+    key: Option<LitStr>,
+    parameters: Vec<Expr>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let method: Ident = input.parse()?;
+        if !["GET", "POST", "PUT", "PATCH", "DELETE"].contains(&method.to_string().as_str()) {
+            return Err(syn::Error::new(
+                method.span(),
+                "expected one of `GET`, `POST`, `PUT`, `PATCH` or `DELETE`",
+            ));
+        }
+        input.parse::<Token![,]>()?;
+        let path: LitStr = input.parse()?;
+
+        let mut args = Args {
+            method,
+            path,
+            paged: false,
+            output: None,
+            key: None,
+            parameters: vec![],
+        };
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let name: Ident = input.parse()?;
+            if name == "paged" {
+                args.paged = true;
+                continue;
+            }
+
+            if !["output", "key", "parameter"].contains(&name.to_string().as_str()) {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "expected `paged`, `output`, `key` or `parameter`",
+                ));
+            }
+
+            input.parse::<Token![=]>()?;
+            if name == "output" {
+                args.output = Some(input.parse()?);
+            } else if name == "key" {
+                args.key = Some(input.parse()?);
+            } else {
+                args.parameters.push(input.parse()?);
+            }
+        }
+
+        if args.paged && args.key.is_some() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`key` can not be used for paged endpoints",
+            ));
+        }
+
+        Ok(args)
+    }
+}
+
+/// A field of the struct that is sent as query parameter.
+struct QueryField {
+    ident: Ident,
+    name: String,
+    kind: FieldKind,
+    /// The type of a single value, inside the `Option` or `Vec`.
+    value_ty: Type,
+    docs: Vec<syn::Attribute>,
+}
+
+enum FieldKind {
+    Required,
+    Optional,
+    Repeated,
+}
+
+fn expand(args: Args, mut item: ItemStruct) -> syn::Result<TokenStream2> {
+    let output = args.output.as_ref().ok_or_else(|| {
+        syn::Error::new(
+            Span::call_site(),
+            "missing the response type: `output = Type`",
+        )
+    })?;
+
+    let path_names = placeholders(&args.path)?;
+    let mut query_fields = vec![];
+    let mut body_fields = vec![];
+
+    match &mut item.fields {
+        Fields::Named(fields) => {
+            for field in &mut fields.named {
+                let ident = field.ident.clone().expect("Named fields have a name");
+                let rename = take_query_attribute(&mut field.attrs)?;
+                let body = take_body_attribute(&mut field.attrs)?;
+
+                if path_names.contains(&ident.to_string()) {
+                    if let Some(rename) = rename {
+                        return Err(syn::Error::new(
+                            rename.span(),
+                            "path arguments can not be renamed",
+                        ));
+                    }
+                    if let Some(body) = body {
+                        return Err(syn::Error::new(
+                            body,
+                            "path arguments can not be sent in the body",
+                        ));
+                    }
+                    continue;
+                }
+
+                if let Some(body) = body {
+                    if args.method == "GET" {
+                        return Err(syn::Error::new(
+                            body,
+                            "a body can not be sent with a `GET` request",
+                        ));
+                    }
+                    if let Some(rename) = rename {
+                        return Err(syn::Error::new(
+                            rename.span(),
+                            "body fields can not be renamed",
+                        ));
+                    }
+
+                    let optional = matches!(field_kind(&field.ty).0, FieldKind::Optional);
+                    body_fields.push((ident, optional));
+                    continue;
+                }
+
+                let (kind, value_ty) = field_kind(&field.ty);
+                let name = match (rename, &kind) {
+                    (Some(rename), _) => rename.value(),
+                    (None, FieldKind::Repeated) => format!("{ident}[]"),
+                    (None, _) => ident.to_string(),
+                };
+                let docs = field
+                    .attrs
+                    .iter()
+                    .filter(|attr| attr.path().is_ident("doc"))
+                    .cloned()
+                    .collect();
+
+                query_fields.push(QueryField {
+                    ident,
+                    name,
+                    kind,
+                    value_ty,
+                    docs,
+                });
+            }
+        }
+        Fields::Unit => {}
+        Fields::Unnamed(fields) => {
+            return Err(syn::Error::new(
+                fields.span(),
+                "endpoints must have named fields",
+            ))
+        }
+    }
+
+    for name in &path_names {
+        let found = item
+            .fields
+            .iter()
+            .any(|field| field.ident.as_ref().is_some_and(|ident| ident == name));
+        if !found {
+            return Err(syn::Error::new(
+                args.path.span(),
+                format!("no field `{name}` for the path argument `{{{name}}}`"),
+            ));
+        }
+    }
+
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    let method = &args.method;
+    let template = &args.path;
+
+    let path = if path_names.is_empty() {
+        quote!(#template.to_string())
+    } else {
+        let idents = path_names
+            .iter()
+            .map(|name| Ident::new(name, Span::call_site()));
+        let values = idents.clone();
+        quote!(format!(
+            #template,
+            #(#idents = ::canvasapi::endpoint::PathSegment(&self.#values)),*
+        ))
+    };
+
+    let parameters = if args.parameters.is_empty() && query_fields.is_empty() {
+        quote!()
+    } else {
+        let fixed = &args.parameters;
+        let pushes = query_fields.iter().map(|field| {
+            let ident = &field.ident;
+            let name = &field.name;
+            match field.kind {
+                FieldKind::Required => quote! {
+                    parameters.push(::canvasapi::parameters::RequestParameter {
+                        name: #name.to_string(),
+                        value: self.#ident.to_string(),
+                    });
+                },
+                FieldKind::Optional => quote! {
+                    if let Some(value) = &self.#ident {
+                        parameters.push(::canvasapi::parameters::RequestParameter {
+                            name: #name.to_string(),
+                            value: value.to_string(),
+                        });
+                    }
+                },
+                FieldKind::Repeated => quote! {
+                    for value in &self.#ident {
+                        parameters.push(::canvasapi::parameters::RequestParameter {
+                            name: #name.to_string(),
+                            value: value.to_string(),
+                        });
+                    }
+                },
+            }
+        });
+
+        quote! {
+            #[allow(unused_mut)]
+            fn parameters(&self) -> Vec<::canvasapi::parameters::RequestParameter> {
+                let mut parameters: Vec<::canvasapi::parameters::RequestParameter> = vec![
+                    #(::canvasapi::parameters::RequestParameter::from(#fixed)),*
+                ];
+                #(#pushes)*
+                parameters
+            }
+        }
+    };
+
+    let body = if body_fields.is_empty() {
+        quote!()
+    } else {
+        let members = body_fields.iter().map(|(ident, optional)| {
+            let name = ident.to_string();
+            if *optional {
+                quote! {
+                    if let Some(value) = &self.#ident {
+                        body = body.field(#name, value)?;
+                    }
+                }
+            } else {
+                quote!(body = body.field(#name, &self.#ident)?;)
+            }
+        });
+
+        quote! {
+            fn body(&self) -> ::canvasapi::endpoint::BodyResult {
+                let mut body = ::canvasapi::endpoint::Body::default();
+                #(#members)*
+                body.finish()
+            }
+        }
+    };
+
+    let (endpoint_output, query, response) = if args.paged {
+        (
+            quote!(#output),
+            quote!(::canvasapi::endpoint::PagedQuery<#name #ty_generics>),
+            quote!(Vec<#output>),
+        )
+    } else if args.key.is_some() {
+        (
+            quote!(::std::collections::HashMap<String, String>),
+            quote!(::canvasapi::endpoint::GetObjectRequestMap<#output, #name #ty_generics>),
+            quote!(#output),
+        )
+    } else {
+        (
+            quote!(#output),
+            quote!(::canvasapi::endpoint::Query<#name #ty_generics>),
+            quote!(#output),
+        )
+    };
+
+    let new_query = match &args.key {
+        Some(key) => quote!(::canvasapi::endpoint::GetObjectRequestMap::new(self, #key)),
+        None if args.paged => quote!(::canvasapi::endpoint::PagedQuery::new(self)),
+        None => quote!(::canvasapi::endpoint::Query::new(self)),
+    };
+
+    let builder = if query_fields.is_empty() {
+        quote!()
+    } else {
+        if !item.generics.params.is_empty() {
+            return Err(syn::Error::new(
+                item.generics.span(),
+                "endpoints with query parameters can not be generic",
+            ));
+        }
+
+        let vis = &item.vis;
+        let trait_name = Ident::new(&format!("{name}Parameters"), name.span());
+        let doc = format!(
+            "The query parameters of [`{name}`], which can be set on the endpoint and on its \
+             request."
+        );
+        let setters = query_fields.iter().map(|field| {
+            let ident = &field.ident;
+            let value_ty = &field.value_ty;
+            let docs = &field.docs;
+            let set = match field.kind {
+                FieldKind::Required => quote!(self.endpoint_mut().#ident = value.into()),
+                FieldKind::Optional => quote!(self.endpoint_mut().#ident = Some(value.into())),
+                FieldKind::Repeated => quote!(self.endpoint_mut().#ident.push(value.into())),
+            };
+
+            quote! {
+                #(#docs)*
+                fn #ident(mut self, value: impl Into<#value_ty>) -> Self {
+                    #set;
+                    self
+                }
+            }
+        });
+
+        quote! {
+            #[doc = #doc]
+            #vis trait #trait_name: Sized {
+                #[doc(hidden)]
+                fn endpoint_mut(&mut self) -> &mut #name;
+
+                #(#setters)*
+            }
+
+            impl #trait_name for #name {
+                fn endpoint_mut(&mut self) -> &mut #name {
+                    self
+                }
+            }
+
+            impl #trait_name for #query {
+                fn endpoint_mut(&mut self) -> &mut #name {
+                    <#query>::endpoint_mut(self)
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        #item
+
+        #builder
+
+        impl #impl_generics ::canvasapi::endpoint::Endpoint for #name #ty_generics #where_clause {
+            type Output = #endpoint_output;
+
+            fn method(&self) -> ::canvasapi::endpoint::Method {
+                ::canvasapi::endpoint::Method::#method
+            }
+
+            fn path(&self) -> String {
+                #path
+            }
+
+            #parameters
+
+            #body
+
+            fn template(&self) -> Option<&'static str> {
+                Some(#template)
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Create the request of this endpoint, to which parameters can be added.
+            pub fn query(self) -> #query {
+                #new_query
+            }
+
+            /// Send the request of this endpoint.
+            pub async fn fetch(
+                self,
+                canvas: &::canvasapi::canvas::CanvasInformation,
+            ) -> Result<
+                ::canvasapi::endpoint::GetObjectResponse<#response>,
+                ::canvasapi::error::CanvasError,
+            > {
+                self.query().fetch(canvas).await
+            }
+        }
+
+        ::canvasapi::__blocking! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Send the request of this endpoint, blocking the current thread until the
+                /// response is received.
+                ///
+                /// # Panics
+                /// Panics when called from within an asynchronous runtime. Use `fetch` there.
+                pub fn fetch_blocking(
+                    self,
+                    canvas: &::canvasapi::canvas::CanvasInformation,
+                ) -> Result<
+                    ::canvasapi::endpoint::GetObjectResponse<#response>,
+                    ::canvasapi::error::CanvasError,
+                > {
+                    self.query().fetch_blocking(canvas)
+                }
+            }
+        }
+    })
+}
+
+/// The names between braces in the path, in order and without duplicates.
+fn placeholders(path: &LitStr) -> syn::Result<Vec<String>> {
+    let value = path.value();
+    let mut names: Vec<String> = vec![];
+    let mut rest = value.as_str();
+
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| syn::Error::new(path.span(), "unclosed `{` in the path"))?;
+        let name = &rest[start + 1..start + end];
+
+        if syn::parse_str::<Ident>(name).is_err() {
+            return Err(syn::Error::new(
+                path.span(),
+                format!("`{{{name}}}` is not a field name"),
+            ));
+        }
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+
+        rest = &rest[start + end + 1..];
+    }
+
+    Ok(names)
+}
+
+/// Remove the `#[query(name = "...")]` attribute of a field, and return the name.
+fn take_query_attribute(attrs: &mut Vec<syn::Attribute>) -> syn::Result<Option<LitStr>> {
+    let mut rename = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("query")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                rename = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `name = \"...\"`"))
+            }
+        })?;
+    }
+    attrs.retain(|attr| !attr.path().is_ident("query"));
+
+    Ok(rename)
+}
+
+/// Remove the `#[body]` attribute of a field, and return its span when it is present.
+fn take_body_attribute(attrs: &mut Vec<syn::Attribute>) -> syn::Result<Option<Span>> {
+    let mut body = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("body")) {
+        attr.meta.require_path_only()?;
+        body = Some(attr.path().span());
+    }
+    attrs.retain(|attr| !attr.path().is_ident("body"));
+
+    Ok(body)
+}
+
+/// How a field is sent, based on the outer type: `Option` when present, `Vec` once per value.
+///
+/// The type of a single value is returned as well.
+fn field_kind(ty: &Type) -> (FieldKind, Type) {
+    let Type::Path(path) = ty else {
+        return (FieldKind::Required, ty.clone());
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return (FieldKind::Required, ty.clone());
+    };
+
+    let kind = if segment.ident == "Option" {
+        FieldKind::Optional
+    } else if segment.ident == "Vec" {
+        FieldKind::Repeated
+    } else {
+        return (FieldKind::Required, ty.clone());
+    };
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(arguments) => match arguments.args.first() {
+            Some(syn::GenericArgument::Type(value_ty)) => (kind, value_ty.clone()),
+            _ => (FieldKind::Required, ty.clone()),
+        },
+        _ => (FieldKind::Required, ty.clone()),
+    }
+}
@@ -21,30 +21,30 @@ enum Commands {
     Courses,
     /// Get all the assignments of a course.
     Assignments {
-        /// The ID of the course.
+        /// The ID of the course, or its SIS ID as `sis_course_id:<id>`.
         #[clap(value_parser)]
-        id: usize,
+        id: CourseId,
     },
     /// Get all the information of a course.
     Course {
-        /// The ID of the course.
+        /// The ID of the course, or its SIS ID as `sis_course_id:<id>`.
         #[clap(value_parser)]
-        id: usize,
+        id: CourseId,
     },
     /// Get the users enrolled to a course.
     Users {
-        /// The ID of the course.
+        /// The ID of the course, or its SIS ID as `sis_course_id:<id>`.
         #[clap(value_parser)]
-        course_id: usize,
+        course_id: CourseId,
         /// The enrolment type of the user.
         #[clap(short, long = "type", value_parser)]
         type_: Option<Enrollment>,
     },
     /// Download files of a course.
     Files {
-        /// The ID of the course.
+        /// The ID of the course, or its SIS ID as `sis_course_id:<id>`.
         #[clap(value_parser)]
-        id: usize,
+        id: CourseId,
         /// The output directory.
         #[clap(long, value_parser)]
         out_dir: Option<std::path::PathBuf>,
@@ -65,7 +65,7 @@ enum Commands {
     Todo {
         /// The ID of the course.
         #[clap(value_parser)]
-        course_id: Option<usize>,
+        course_id: Option<CourseId>,
     },
 }
 
@@ -99,8 +99,7 @@ async fn main() {
     let args = Args::parse();
 
     let base_url = if let Some(url) = args.url {
-        // TODO: check that the URL is valid.
-        url.to_string()
+        url
     } else {
         std::env::var("CANVAS_BASE_URL")
             .expect("Pass the Canvas URL as an environment variable, or pass it to the command.")
@@ -113,7 +112,10 @@ async fn main() {
             .expect("Pass the Canvas access token using an environment variable, or pass it to the command.")
     };
 
-    let canvas = CanvasInformation::new(&base_url, &canvas_token);
+    let canvas = CanvasInformation::builder(&base_url, canvas_token)
+        .user_agent(concat!("canvas/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .expect("Invalid Canvas URL.");
 
     let data = match args.command {
         Commands::Courses => {
@@ -165,7 +167,7 @@ async fn main() {
             let users = course.get_users().unwrap();
 
             let users = if let Some(type_) = type_ {
-                users.add_parameter(<Enrollment as Into<EnrollmentType>>::into(type_))
+                users.enrollment_type(type_)
             } else {
                 users
             };
@@ -235,9 +237,23 @@ async fn main() {
                 .inner();
 
             let results = if let Some(course_id) = course_id {
+                // The todo items refer to their course by its Canvas id, so a SIS id is resolved
+                // first.
+                let course_id = match course_id {
+                    CourseId::Sis(_) => {
+                        Canvas::get_course(course_id)
+                            .unwrap()
+                            .fetch(&canvas)
+                            .await
+                            .unwrap()
+                            .inner()
+                            .id
+                    }
+                    id => id,
+                };
                 results
                     .into_iter()
-                    .filter(|item| item.course_id == Some(course_id as i64))
+                    .filter(|item| item.course_id.as_ref() == Some(&course_id))
                     .collect()
             } else {
                 results
@@ -0,0 +1,379 @@
+//! Caching of the responses of get requests.
+//!
+//! With a [`Cache`], the responses of successful get requests are stored, keyed by the url and the
+//! access token. A cached response is used without contacting Canvas while it is younger than the
+//! time to live. Older responses are revalidated with the `If-None-Match` and `If-Modified-Since`
+//! headers, based on the `ETag` and `Last-Modified` headers of the cached response, and the
+//! cached body is returned when Canvas answers with `304 Not Modified`.
+//!
+//! Every page of a paged request is cached on its own. Only JSON responses are cached, such that
+//! the downloads of files are not kept in the cache.
+//!
+//! The size of the cache is bounded by [`Cache::max_bytes`]. When the cache grows beyond it, the
+//! least recently used responses are removed from a memory cache, and the oldest files from a disk
+//! cache.
+//!
+//! # Example
+//!
+//! ```
+//! # use canvasapi::prelude::*;
+//! # use std::time::Duration;
+//! use canvasapi::cache::Cache;
+//!
+//! let canvas = CanvasInformation::builder("https://canvas.test.be", "MY_TOKEN")
+//!     .cache(Cache::memory().ttl(Duration::from_secs(60)))
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::cassette::RecordedResponse;
+use crate::error::CanvasError;
+use crate::transport::{HttpRequest, HttpResponse};
+
+/// A cache for the responses of get requests.
+///
+/// The cache is either kept in memory, or stored on disk such that it can be reused by later runs.
+/// Failing to read or write the cache on disk is not an error: the request is then sent to Canvas
+/// as if the response was not cached.
+#[derive(Debug)]
+pub struct Cache {
+    storage: Storage,
+    ttl: Duration,
+    max_bytes: u64,
+}
+
+/// The default bound of the size of a cache, 64 MiB.
+const DEFAULT_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug)]
+enum Storage {
+    Memory(Mutex<Memory>),
+    Disk(PathBuf),
+}
+
+/// The responses of a memory cache.
+#[derive(Debug, Default)]
+struct Memory {
+    entries: HashMap<String, (Entry, u64)>,
+    /// The total size of the cached bodies.
+    bytes: u64,
+    /// A counter that orders the entries by their last use.
+    clock: u64,
+}
+
+/// A cached response.
+#[derive(Debug, Clone)]
+struct Entry {
+    stored_at: SystemTime,
+    response: HttpResponse,
+}
+
+/// A cached response, as stored on disk.
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    key: String,
+    /// The time the response was stored, in seconds since the Unix epoch.
+    stored_at: u64,
+    response: RecordedResponse,
+}
+
+impl Cache {
+    /// A cache that is kept in memory.
+    pub fn memory() -> Self {
+        Self {
+            storage: Storage::Memory(Mutex::default()),
+            ttl: Duration::ZERO,
+            max_bytes: DEFAULT_MAX_BYTES,
+        }
+    }
+
+    /// A cache that is stored in the directory `path`, with a file per response.
+    ///
+    /// The directory is created when it does not exist.
+    pub fn disk(path: impl Into<PathBuf>) -> Self {
+        Self {
+            storage: Storage::Disk(path.into()),
+            ttl: Duration::ZERO,
+            max_bytes: DEFAULT_MAX_BYTES,
+        }
+    }
+
+    /// Use cached responses without revalidating them while they are younger than `ttl`.
+    ///
+    /// By default, the time to live is zero and every cached response is revalidated.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Bound the size of the cache to `max_bytes`: the total size of the cached bodies in memory,
+    /// or of the cached files on disk.
+    ///
+    /// Responses that are larger than the bound are not cached. By default, the bound is 64 MiB.
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Remove all the cached responses.
+    pub fn clear(&self) -> Result<(), CanvasError> {
+        match &self.storage {
+            Storage::Memory(memory) => *memory.lock().unwrap() = Memory::default(),
+            Storage::Disk(path) if path.exists() => {
+                for file in std::fs::read_dir(path)? {
+                    let file = file?.path();
+                    if file
+                        .extension()
+                        .is_some_and(|extension| extension == "json")
+                    {
+                        std::fs::remove_file(file)?;
+                    }
+                }
+            }
+            Storage::Disk(_) => {}
+        }
+
+        Ok(())
+    }
+
+    /// Send a get request, using the cached response when possible.
+    pub(crate) async fn send<F, Fut>(
+        &self,
+        token: &str,
+        mut request: HttpRequest,
+        send: F,
+    ) -> Result<HttpResponse, CanvasError>
+    where
+        F: FnOnce(HttpRequest) -> Fut,
+        Fut: Future<Output = Result<HttpResponse, CanvasError>>,
+    {
+        let key = cache_key(token, request.url.as_str());
+        let cached = self.load(&key);
+
+        if let Some(entry) = &cached {
+            if entry.age() < self.ttl {
+                event!(DEBUG, path = request.url.path(), "cached response");
+                return Ok(entry.response.clone());
+            }
+
+            if let Some(etag) = entry.response.headers.get(ETAG) {
+                request.headers.insert(IF_NONE_MATCH, etag.clone());
+            }
+
+            if let Some(last_modified) = entry.response.headers.get(LAST_MODIFIED) {
+                request
+                    .headers
+                    .insert(IF_MODIFIED_SINCE, last_modified.clone());
+            }
+        }
+
+        let response = send(request).await?;
+
+        match cached {
+            Some(mut entry) if response.status == StatusCode::NOT_MODIFIED => {
+                event!(DEBUG, "cached response is not modified");
+
+                // Update the cached headers, for example the rate limit, with the new values.
+                for (name, value) in &response.headers {
+                    entry.response.headers.insert(name, value.clone());
+                }
+                entry.stored_at = SystemTime::now();
+                self.store(&key, &entry);
+
+                Ok(entry.response)
+            }
+            _ => {
+                if response.status.is_success()
+                    && is_json(&response)
+                    && (!self.ttl.is_zero() || has_validator(&response))
+                {
+                    let entry = Entry {
+                        stored_at: SystemTime::now(),
+                        response: response.clone(),
+                    };
+                    self.store(&key, &entry);
+                }
+
+                Ok(response)
+            }
+        }
+    }
+
+    fn load(&self, key: &str) -> Option<Entry> {
+        match &self.storage {
+            Storage::Memory(memory) => {
+                let mut memory = memory.lock().unwrap();
+                memory.clock += 1;
+                let clock = memory.clock;
+                let (entry, used) = memory.entries.get_mut(key)?;
+                *used = clock;
+                Some(entry.clone())
+            }
+            Storage::Disk(path) => {
+                let content = std::fs::read(path.join(file_name(key))).ok()?;
+                let entry: DiskEntry = serde_json::from_slice(&content).ok()?;
+
+                // Different keys can have the same file name.
+                if entry.key != key {
+                    return None;
+                }
+
+                Some(Entry {
+                    stored_at: SystemTime::UNIX_EPOCH + Duration::from_secs(entry.stored_at),
+                    response: entry.response.to_response().ok()?,
+                })
+            }
+        }
+    }
+
+    fn store(&self, key: &str, entry: &Entry) {
+        if entry.size() > self.max_bytes {
+            self.remove(key);
+            return;
+        }
+
+        match &self.storage {
+            Storage::Memory(memory) => {
+                let mut memory = memory.lock().unwrap();
+                memory.clock += 1;
+                let clock = memory.clock;
+                memory.bytes += entry.size();
+                if let Some((old, _)) = memory
+                    .entries
+                    .insert(key.to_string(), (entry.clone(), clock))
+                {
+                    memory.bytes -= old.size();
+                }
+
+                while memory.bytes > self.max_bytes {
+                    let Some(lru) = memory
+                        .entries
+                        .iter()
+                        .min_by_key(|(_, (_, used))| *used)
+                        .map(|(key, _)| key.clone())
+                    else {
+                        break;
+                    };
+                    if let Some((old, _)) = memory.entries.remove(&lru) {
+                        memory.bytes -= old.size();
+                    }
+                }
+            }
+            Storage::Disk(path) => {
+                let entry = DiskEntry {
+                    key: key.to_string(),
+                    stored_at: entry
+                        .stored_at
+                        .duration_since(SystemTime::UNIX_EPOCH)
+                        .map_or(0, |time| time.as_secs()),
+                    response: RecordedResponse::from(&entry.response),
+                };
+
+                if let Ok(content) = serde_json::to_vec(&entry) {
+                    let _ = std::fs::create_dir_all(path)
+                        .and_then(|_| std::fs::write(path.join(file_name(&entry.key)), content))
+                        .and_then(|_| remove_oldest_files(path, self.max_bytes));
+                }
+            }
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        match &self.storage {
+            Storage::Memory(memory) => {
+                let mut memory = memory.lock().unwrap();
+                if let Some((old, _)) = memory.entries.remove(key) {
+                    memory.bytes -= old.size();
+                }
+            }
+            Storage::Disk(path) => {
+                let _ = std::fs::remove_file(path.join(file_name(key)));
+            }
+        }
+    }
+}
+
+impl Entry {
+    fn age(&self) -> Duration {
+        self.stored_at.elapsed().unwrap_or_default()
+    }
+
+    /// The size of the cached body.
+    fn size(&self) -> u64 {
+        self.response.body.len() as u64
+    }
+}
+
+/// Remove the oldest files of a disk cache until their total size is at most `max_bytes`.
+fn remove_oldest_files(path: &std::path::Path, max_bytes: u64) -> std::io::Result<()> {
+    let mut files = Vec::new();
+    for file in std::fs::read_dir(path)? {
+        let file = file?;
+        let metadata = file.metadata()?;
+        if file
+            .path()
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            files.push((metadata.modified()?, metadata.len(), file.path()));
+        }
+    }
+
+    let mut bytes: u64 = files.iter().map(|(_, len, _)| len).sum();
+    files.sort();
+    for (_, len, file) in files {
+        if bytes <= max_bytes {
+            break;
+        }
+        std::fs::remove_file(file)?;
+        bytes -= len;
+    }
+
+    Ok(())
+}
+
+/// Check if the response has a JSON body.
+fn is_json(response: &HttpResponse) -> bool {
+    response
+        .headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.trim_start().starts_with("application/json"))
+}
+
+/// Check if the response can be revalidated.
+fn has_validator(response: &HttpResponse) -> bool {
+    response.headers.contains_key(ETAG) || response.headers.contains_key(LAST_MODIFIED)
+}
+
+/// The key of a response, from the url and the identity of the access token.
+///
+/// The token itself is not part of the key, such that it is not stored on disk.
+fn cache_key(token: &str, url: &str) -> String {
+    format!("{:016x} {url}", hash(token))
+}
+
+/// The name of the file in which the response with the given key is stored.
+fn file_name(key: &str) -> String {
+    format!("{:016x}.json", hash(key))
+}
+
+/// The 64-bit FNV-1a hash of a value.
+///
+/// Unlike the hasher of the standard library, the hash is stable across Rust releases, such that a
+/// disk cache can be reused after a toolchain upgrade.
+fn hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
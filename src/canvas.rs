@@ -1,46 +1,759 @@
 //! Canvas structs that are not part of the API, but are needed for the library.
 
+use std::ops::ControlFlow;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::Client;
+use url::Url;
+
+use crate::cache::Cache;
+use crate::error::CanvasError;
+use crate::ids::UserId;
 use crate::models::prelude::*;
+use crate::oauth::{OAuthClient, OAuthToken, TOKEN_PATH};
 use crate::requests::*;
+use crate::transport::{HttpRequest, HttpResponse, ReqwestTransport, Transport};
 
 /// Contains information about the server URL and the API access token.
 ///
+/// The HTTP client is created once and reused for all requests made with this information,
+/// such that connections are pooled.
+/// The requests are sent with `reqwest`, unless another [`Transport`] is configured.
+/// The information is reference counted, so cloning it is cheap and the clones can be moved into
+/// other threads or tasks.
+///
 /// # Example
 /// ```
 /// # use canvasapi::canvas::CanvasInformation;
 /// let canvas = CanvasInformation::new("https://canvas.test.be", "MY_TOKEN");
 /// ```
-#[derive(Debug)]
-pub struct CanvasInformation<'i> {
-    base_url: &'i str,
-    token: &'i str,
+#[derive(Clone)]
+pub struct CanvasInformation {
+    inner: Arc<CanvasInformationInner>,
+}
+
+struct CanvasInformationInner {
+    base_url: Url,
+    credentials: Mutex<Credentials>,
+    oauth_client: Option<OAuthClient>,
+    /// Held while the token is refreshed, such that concurrent requests refresh it only once.
+    refresh_lock: tokio::sync::Mutex<()>,
+    transport: Arc<dyn Transport>,
+    throttle: Option<Throttle>,
+    retry_policy: Option<RetryPolicy>,
+    cache: Option<Cache>,
+    masquerade: Option<UserId>,
+    rate_limit_remaining: Mutex<Option<f64>>,
+    #[cfg(feature = "blocking")]
+    runtime: BlockingRuntime,
+}
+
+impl std::fmt::Debug for CanvasInformation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CanvasInformation")
+            .field("base_url", &self.inner.base_url.as_str())
+            .field("token", &"<redacted>")
+            .field("oauth_client", &self.inner.oauth_client)
+            .field("throttle", &self.inner.throttle)
+            .field("retry_policy", &self.inner.retry_policy)
+            .field("cache", &self.inner.cache)
+            .field("masquerade", &self.inner.masquerade)
+            .finish()
+    }
 }
 
-impl<'i> CanvasInformation<'i> {
+impl CanvasInformation {
     /// Create a new instance of the Canvas API.
-    pub fn new(base_url: &'i str, token: &'i str) -> Self {
-        Self { base_url, token }
+    ///
+    /// This is a shortcut for [`CanvasInformation::builder`] without any extra configuration.
+    ///
+    /// # Panics
+    /// Panics when the base URL is not a valid HTTP(S) URL, or when the HTTP client can not be
+    /// created. Use [`CanvasInformation::builder`] to handle these errors.
+    pub fn new(base_url: &str, token: &str) -> Self {
+        Self::builder(base_url, token)
+            .build()
+            .expect("Failed to create the Canvas information")
+    }
+
+    /// Create a new instance of the Canvas API, using a preconfigured client.
+    ///
+    /// # Panics
+    /// Panics when the base URL is not a valid HTTP(S) URL.
+    ///
+    /// # Example
+    /// ```
+    /// # use canvasapi::canvas::CanvasInformation;
+    /// let client = reqwest::Client::builder()
+    ///     .timeout(std::time::Duration::from_secs(30))
+    ///     .build()
+    ///     .unwrap();
+    /// let canvas = CanvasInformation::with_client("https://canvas.test.be", "MY_TOKEN", client);
+    /// ```
+    pub fn with_client(base_url: &str, token: &str, client: Client) -> Self {
+        Self::builder(base_url, token)
+            .client(client)
+            .build()
+            .expect("Failed to create the Canvas information")
+    }
+
+    /// Create a builder for configuring the Canvas information.
+    ///
+    /// # Example
+    /// ```
+    /// # use canvasapi::canvas::CanvasInformation;
+    /// # use std::time::Duration;
+    /// let canvas = CanvasInformation::builder("https://canvas.test.be", "MY_TOKEN")
+    ///     .timeout(Duration::from_secs(30))
+    ///     .connect_timeout(Duration::from_secs(5))
+    ///     .user_agent("grading-scripts/1.0")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder(base_url: &str, token: impl Into<String>) -> CanvasInformationBuilder {
+        CanvasInformationBuilder {
+            base_url: base_url.to_string(),
+            token: OAuthToken::new(token),
+            oauth_client: None,
+            timeout: None,
+            connect_timeout: None,
+            user_agent: None,
+            proxies: vec![],
+            default_headers: HeaderMap::new(),
+            transport: None,
+            throttle: None,
+            retry_policy: None,
+            cache: None,
+            masquerade: None,
+        }
+    }
+
+    /// Create a builder for the Canvas information of a user that authorized the application with
+    /// OAuth2.
+    ///
+    /// The token is refreshed with the client when it expires, as with
+    /// [`CanvasInformationBuilder::oauth`].
+    pub fn oauth_builder(
+        base_url: &str,
+        client: OAuthClient,
+        token: OAuthToken,
+    ) -> CanvasInformationBuilder {
+        Self::builder(base_url, token.access_token.clone()).oauth(client, token)
+    }
+
+    /// The base URL of the Canvas server.
+    pub fn base_url(&self) -> &Url {
+        &self.inner.base_url
+    }
+
+    /// The current OAuth2 token, when the information is built with
+    /// [`CanvasInformationBuilder::oauth`].
+    ///
+    /// The token changes when it is refreshed, so it should be stored after the requests are made.
+    pub fn oauth_token(&self) -> Option<OAuthToken> {
+        self.inner
+            .oauth_client
+            .as_ref()
+            .map(|_| self.credentials().token)
+    }
+
+    /// Delete the access token, which logs the user out of the application.
+    ///
+    /// The requests that are made with the information afterwards fail.
+    pub async fn delete_token(&self) -> Result<(), CanvasError> {
+        let url = format!(
+            "{}/{TOKEN_PATH}",
+            self.inner.base_url.as_str().trim_end_matches('/')
+        );
+        let request = self.request(reqwest::Method::DELETE, url)?;
+        self.send(request).await?.error_for_status()?;
+        Ok(())
+    }
+
+    /// Delete the access token, which logs the user out of the application.
+    ///
+    /// The requests that are made with the information afterwards fail.
+    #[cfg(feature = "blocking")]
+    pub fn delete_token_blocking(&self) -> Result<(), CanvasError> {
+        self.block_on(self.delete_token())
+    }
+
+    /// The user as which all the requests are made, when masquerading.
+    pub fn masquerade(&self) -> Option<&UserId> {
+        self.inner.masquerade.as_ref()
+    }
+
+    /// The remaining rate limit quota, as reported by Canvas in the last response.
+    ///
+    /// This is tracked for every response from Canvas, also without a [`Throttle`]. Responses that
+    /// are served from the [`Cache`] without contacting Canvas do not change it.
+    pub fn rate_limit_remaining(&self) -> Option<f64> {
+        *self.inner.rate_limit_remaining.lock().unwrap()
     }
 
     pub(crate) fn add_url_prefix(&self, url: &str) -> String {
-        format!("{}/api/v1/{}", self.base_url, url)
+        format!(
+            "{}/api/v1/{}",
+            self.inner.base_url.as_str().trim_end_matches('/'),
+            url
+        )
+    }
+
+    /// The url of the GraphQL API.
+    pub(crate) fn graphql_url(&self) -> String {
+        format!(
+            "{}/api/graphql",
+            self.inner.base_url.as_str().trim_end_matches('/')
+        )
+    }
+
+    /// Create a get request.
+    pub(crate) fn get_request(&self, url: String) -> Result<HttpRequest, CanvasError> {
+        self.request(reqwest::Method::GET, url)
+    }
+
+    /// Create a request with the given method.
+    /// The token is added when the request is sent.
+    pub(crate) fn request(
+        &self,
+        method: reqwest::Method,
+        url: String,
+    ) -> Result<HttpRequest, CanvasError> {
+        let url = Url::parse(&url)
+            .map_err(|e| CanvasError::InvalidRequest(format!("Invalid url `{url}`: {e}")))?;
+
+        Ok(HttpRequest {
+            method,
+            url,
+            headers: HeaderMap::new(),
+            body: None,
+        })
+    }
+
+    /// Send a request and read the complete response.
+    ///
+    /// The rate limiting of Canvas is honoured when a [`Throttle`] is configured, and transient
+    /// failures are retried according to the [`RetryPolicy`].
+    /// Get requests are answered from the [`Cache`] when possible.
+    /// An expired OAuth2 token is refreshed, after which the request is sent again.
+    pub(crate) async fn send(&self, request: HttpRequest) -> Result<HttpResponse, CanvasError> {
+        self.send_with_cache(request, true).await
+    }
+
+    /// Send a request like [`send`](Self::send), without using the [`Cache`].
+    ///
+    /// This is used for the downloads of files, which should not be kept in the cache.
+    pub(crate) async fn send_bypassing_cache(
+        &self,
+        request: HttpRequest,
+    ) -> Result<HttpResponse, CanvasError> {
+        self.send_with_cache(request, false).await
+    }
+
+    async fn send_with_cache(
+        &self,
+        request: HttpRequest,
+        cached: bool,
+    ) -> Result<HttpResponse, CanvasError> {
+        let credentials = self.credentials();
+        let resp = self
+            .send_authorized(&credentials, request.clone(), cached)
+            .await?;
+
+        match &self.inner.oauth_client {
+            Some(client) if credentials.token.needs_refresh(&resp) => {
+                let credentials = self.refresh(client, &credentials).await?;
+                self.send_authorized(&credentials, request, cached).await
+            }
+            _ => Ok(resp),
+        }
+    }
+
+    /// Send a request with the access token of the credentials.
+    async fn send_authorized(
+        &self,
+        credentials: &Credentials,
+        mut request: HttpRequest,
+        cached: bool,
+    ) -> Result<HttpResponse, CanvasError> {
+        request.headers.insert(
+            reqwest::header::AUTHORIZATION,
+            credentials.authorization.clone(),
+        );
+
+        match &self.inner.cache {
+            Some(cache) if cached && request.method == reqwest::Method::GET => {
+                cache
+                    .send(credentials.identity(), request, |request| {
+                        self.send_uncached(request)
+                    })
+                    .await
+            }
+            _ => self.send_uncached(request).await,
+        }
+    }
+
+    /// Refresh the OAuth2 token, unless another request already refreshed the token that was used.
+    async fn refresh(
+        &self,
+        client: &OAuthClient,
+        used: &Credentials,
+    ) -> Result<Credentials, CanvasError> {
+        let _guard = self.inner.refresh_lock.lock().await;
+
+        let current = self.credentials();
+        if current.token.access_token != used.token.access_token {
+            return Ok(current);
+        }
+
+        event!(DEBUG, "refreshing the OAuth2 token");
+        let token = client.refresh(&current.token).await?;
+        let credentials = Credentials::new(token).map_err(|_| {
+            CanvasError::InvalidResponse("Invalid characters in the access token".into())
+        })?;
+        *self.inner.credentials.lock().unwrap() = credentials.clone();
+
+        Ok(credentials)
+    }
+
+    fn credentials(&self) -> Credentials {
+        self.inner.credentials.lock().unwrap().clone()
+    }
+
+    /// Send a request without using the cache.
+    async fn send_uncached(&self, request: HttpRequest) -> Result<HttpResponse, CanvasError> {
+        let mut attempts = 0;
+        let mut throttled = 0;
+
+        loop {
+            if let Some(delay) = self.throttle_delay() {
+                event!(DEBUG, delay_ms = delay.as_millis() as u64, "throttling");
+                tokio::time::sleep(delay).await;
+            }
+
+            attempts += 1;
+            #[cfg(feature = "tracing")]
+            let start = std::time::Instant::now();
+            let result = self.inner.transport.send(request.clone()).await;
+
+            match &result {
+                Ok(resp) => event!(
+                    DEBUG,
+                    method = %request.method,
+                    path = request.url.path(),
+                    attempt = attempts,
+                    status = resp.status.as_u16(),
+                    latency_ms = start.elapsed().as_millis() as u64,
+                    rate_limit_remaining = resp.rate_limit_remaining(),
+                    request_cost = resp.metadata().request_cost(),
+                    "response"
+                ),
+                Err(error) => event!(
+                    WARN,
+                    method = %request.method,
+                    path = request.url.path(),
+                    attempt = attempts,
+                    latency_ms = start.elapsed().as_millis() as u64,
+                    %error,
+                    "request failed"
+                ),
+            }
+
+            match self.next_attempt(&request.method, result, attempts, &mut throttled) {
+                ControlFlow::Continue(wait) => {
+                    event!(DEBUG, wait_ms = wait.as_millis() as u64, "retrying");
+                    tokio::time::sleep(wait).await
+                }
+                ControlFlow::Break(result) => return result,
+            }
+        }
+    }
+
+    /// The delay before sending a request, when the rate limit quota runs low.
+    fn throttle_delay(&self) -> Option<Duration> {
+        self.inner
+            .throttle
+            .as_ref()?
+            .delay(self.rate_limit_remaining())
+    }
+
+    /// Decide whether the result of an attempt is returned, or how long to wait before the next
+    /// attempt.
+    fn next_attempt(
+        &self,
+        method: &reqwest::Method,
+        result: Result<HttpResponse, CanvasError>,
+        attempts: usize,
+        throttled: &mut usize,
+    ) -> ControlFlow<Result<HttpResponse, CanvasError>, Duration> {
+        let retry_policy = self
+            .inner
+            .retry_policy
+            .as_ref()
+            .filter(|policy| policy.applies_to(method));
+
+        match result {
+            Ok(resp) => {
+                self.update_rate_limit(&resp);
+
+                if let Some(throttle) = &self.inner.throttle {
+                    if let Some(wait) = throttle.retry_wait(&resp, *throttled) {
+                        *throttled += 1;
+                        return ControlFlow::Continue(wait);
+                    }
+                }
+
+                match retry_policy
+                    .and_then(|policy| policy.retry_status(resp.status, &resp.headers, attempts))
+                {
+                    Some(wait) => ControlFlow::Continue(wait),
+                    None => ControlFlow::Break(Ok(resp)),
+                }
+            }
+            Err(error) => {
+                match retry_policy.and_then(|policy| policy.retry_error(&error, attempts)) {
+                    Some(wait) => ControlFlow::Continue(wait),
+                    None => ControlFlow::Break(Err(error)),
+                }
+            }
+        }
     }
 
-    /// Create a client for a get request.
-    /// This adds the url and the token.
-    #[cfg(not(feature = "blocking"))]
-    pub(crate) fn get_request(&self, url: String) -> reqwest::RequestBuilder {
-        reqwest::Client::new().get(&url).bearer_auth(self.token)
+    /// Store the remaining rate limit quota from the response headers.
+    fn update_rate_limit(&self, resp: &HttpResponse) {
+        if let Some(remaining) = resp.rate_limit_remaining() {
+            *self.inner.rate_limit_remaining.lock().unwrap() = Some(remaining);
+        }
     }
 
+    /// Run a request to completion on the runtime of the blocking API.
+    ///
+    /// # Panics
+    /// Panics when called from within an asynchronous runtime.
     #[cfg(feature = "blocking")]
-    pub(crate) fn get_request(&self, url: String) -> reqwest::blocking::RequestBuilder {
-        reqwest::blocking::Client::new()
-            .get(&url)
-            .bearer_auth(self.token)
+    pub(crate) fn block_on<T>(
+        &self,
+        future: impl std::future::Future<Output = Result<T, CanvasError>>,
+    ) -> Result<T, CanvasError> {
+        self.inner.runtime.block_on(future)
     }
+}
+
+/// The access token, and the `Authorization` header that is created from it.
+#[derive(Clone)]
+struct Credentials {
+    token: OAuthToken,
+    authorization: HeaderValue,
+}
 
-    pub(crate) fn get_token(&self) -> &str {
+impl Credentials {
+    fn new(token: OAuthToken) -> Result<Self, reqwest::header::InvalidHeaderValue> {
+        let mut authorization = HeaderValue::try_from(format!("Bearer {}", token.access_token))?;
+        authorization.set_sensitive(true);
+
+        Ok(Self {
+            token,
+            authorization,
+        })
+    }
+
+    /// The identity of the token, which does not change when an OAuth2 token is refreshed.
+    fn identity(&self) -> &str {
         self.token
+            .refresh_token
+            .as_deref()
+            .unwrap_or(&self.token.access_token)
+    }
+}
+
+/// The runtime on which the requests of the blocking API are executed.
+///
+/// The runtime is only created when the first blocking request is made, and is shared by all the
+/// clones of the Canvas information, such that the connections of the client are reused.
+#[cfg(feature = "blocking")]
+#[derive(Default)]
+struct BlockingRuntime(std::sync::OnceLock<tokio::runtime::Runtime>);
+
+#[cfg(feature = "blocking")]
+impl BlockingRuntime {
+    fn block_on<T>(
+        &self,
+        future: impl std::future::Future<Output = Result<T, CanvasError>>,
+    ) -> Result<T, CanvasError> {
+        if self.0.get().is_none() {
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(1)
+                .thread_name("canvasapi-blocking")
+                .enable_all()
+                .build()?;
+
+            if let Err(runtime) = self.0.set(runtime) {
+                runtime.shutdown_background();
+            }
+        }
+
+        self.0
+            .get()
+            .expect("The runtime is created above")
+            .block_on(future)
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Drop for BlockingRuntime {
+    fn drop(&mut self) {
+        // Dropping a runtime blocks, which is not allowed when the last clone of the Canvas
+        // information is dropped in an asynchronous context.
+        if let Some(runtime) = self.0.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+/// Builder for [`CanvasInformation`].
+///
+/// Created with [`CanvasInformation::builder`].
+#[must_use]
+pub struct CanvasInformationBuilder {
+    base_url: String,
+    token: OAuthToken,
+    oauth_client: Option<OAuthClient>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    proxies: Vec<reqwest::Proxy>,
+    default_headers: HeaderMap,
+    transport: Option<Arc<dyn Transport>>,
+    throttle: Option<Throttle>,
+    retry_policy: Option<RetryPolicy>,
+    cache: Option<Cache>,
+    masquerade: Option<UserId>,
+}
+
+impl CanvasInformationBuilder {
+    /// Set the timeout for a complete request, from connecting until the body is read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timeout for connecting to the Canvas server.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the `User-Agent` header that is sent with every request.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Add a proxy that is used for the requests.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Add headers that are sent with every request.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+        self
+    }
+
+    /// Use a preconfigured client.
+    ///
+    /// The client is used as is: the timeouts, user agent, proxies and default headers of this
+    /// builder are not applied to it.
+    pub fn client(self, client: Client) -> Self {
+        self.transport(ReqwestTransport::new(client))
+    }
+
+    /// Send the requests with another transport than `reqwest`.
+    ///
+    /// The timeouts, user agent, proxies and default headers of this builder are not applied to
+    /// the transport. The throttle and retry policy are applied on top of it.
+    pub fn transport(mut self, transport: impl Transport) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Honour the rate limiting of Canvas.
+    ///
+    /// By default, requests are sent as fast as possible and a throttled request fails.
+    pub fn throttle(mut self, throttle: Throttle) -> Self {
+        self.throttle = Some(throttle);
+        self
+    }
+
+    /// Retry requests that failed because of a transient error.
+    ///
+    /// By default, requests are not retried.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Cache the responses of get requests.
+    ///
+    /// By default, responses are not cached.
+    pub fn cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Make all the requests as another user, which Canvas calls masquerading.
+    ///
+    /// The user of the access token needs the permission to become other users. The user can
+    /// also be given by its SIS id, with [`UserId::sis`]. The links to the other pages of a paged
+    /// request keep the user. A user that is set on a single request, with its `masquerade`
+    /// method, takes precedence.
+    pub fn masquerade(mut self, user: impl Into<UserId>) -> Self {
+        self.masquerade = Some(user.into());
+        self
+    }
+
+    /// Use an OAuth2 token, which is refreshed with the client when it expires.
+    ///
+    /// This replaces the access token that was given to [`CanvasInformation::builder`].
+    pub fn oauth(mut self, client: OAuthClient, token: OAuthToken) -> Self {
+        self.token = token;
+        self.oauth_client = Some(client);
+        self
+    }
+
+    /// Validate the base URL and the token, and create the HTTP client.
+    pub fn build(self) -> Result<CanvasInformation, CanvasError> {
+        let base_url = parse_base_url(&self.base_url)?;
+
+        let credentials = Credentials::new(self.token)
+            .map_err(|_| CanvasError::Config("Invalid characters in the access token".into()))?;
+
+        let transport = match self.transport {
+            Some(transport) => transport,
+            None => {
+                let mut builder = Client::builder().default_headers(self.default_headers);
+
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+
+                for proxy in self.proxies {
+                    builder = builder.proxy(proxy);
+                }
+
+                Arc::new(ReqwestTransport::new(builder.build()?))
+            }
+        };
+
+        Ok(CanvasInformation {
+            inner: Arc::new(CanvasInformationInner {
+                base_url,
+                credentials: Mutex::new(credentials),
+                oauth_client: self.oauth_client,
+                refresh_lock: tokio::sync::Mutex::new(()),
+                transport,
+                throttle: self.throttle,
+                retry_policy: self.retry_policy,
+                cache: self.cache,
+                masquerade: self.masquerade,
+                rate_limit_remaining: Mutex::new(None),
+                #[cfg(feature = "blocking")]
+                runtime: BlockingRuntime::default(),
+            }),
+        })
+    }
+}
+
+/// Parse the base URL of a Canvas server, which must be an HTTP(S) URL with a host.
+pub(crate) fn parse_base_url(base_url: &str) -> Result<Url, CanvasError> {
+    let url = Url::parse(base_url)
+        .map_err(|e| CanvasError::Config(format!("Invalid base URL `{base_url}`: {e}")))?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(CanvasError::Config(format!(
+            "Invalid base URL `{base_url}`: expected an HTTP(S) URL"
+        )));
+    }
+
+    if url.cannot_be_a_base() || url.host().is_none() {
+        return Err(CanvasError::Config(format!(
+            "Invalid base URL `{base_url}`: no host"
+        )));
+    }
+
+    Ok(url)
+}
+
+/// Settings for honouring the rate limiting of Canvas.
+///
+/// Canvas reports the remaining quota of an access token in the `X-Rate-Limit-Remaining` header
+/// and answers with `403 Forbidden (Rate Limit Exceeded)` when the quota is used up.
+/// With a throttle, requests are slowed down when the quota runs low, and throttled requests are
+/// retried after a while.
+///
+/// # Example
+/// ```
+/// # use canvasapi::canvas::{CanvasInformation, Throttle};
+/// let canvas = CanvasInformation::builder("https://canvas.test.be", "MY_TOKEN")
+///     .throttle(Throttle::default())
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    /// Requests are slowed down when the remaining quota drops below this value.
+    pub threshold: f64,
+    /// The delay before a request when the quota is used up.
+    /// The delay grows linearly from zero at the threshold to this value.
+    pub max_delay: Duration,
+    /// The time to wait before retrying a throttled request.
+    /// This time is multiplied by the number of the retry.
+    pub retry_wait: Duration,
+    /// The maximum number of retries for a throttled request.
+    pub max_retries: usize,
+}
+
+impl Default for Throttle {
+    fn default() -> Self {
+        Self {
+            threshold: 200.0,
+            max_delay: Duration::from_secs(2),
+            retry_wait: Duration::from_secs(5),
+            max_retries: 5,
+        }
+    }
+}
+
+impl Throttle {
+    /// The delay before sending a request, based on the last known remaining quota.
+    fn delay(&self, remaining: Option<f64>) -> Option<Duration> {
+        let remaining = remaining?;
+
+        if remaining >= self.threshold || self.threshold <= 0.0 {
+            return None;
+        }
+
+        let fraction = ((self.threshold - remaining) / self.threshold).clamp(0.0, 1.0);
+        Some(self.max_delay.mul_f64(fraction))
+    }
+
+    /// The time to wait before retrying, or `None` when the response should be returned.
+    fn retry_wait(&self, resp: &HttpResponse, retries: usize) -> Option<Duration> {
+        if resp.is_rate_limited() && retries < self.max_retries {
+            Some(self.retry_wait * (retries as u32 + 1))
+        } else {
+            None
+        }
     }
 }
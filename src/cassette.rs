@@ -0,0 +1,579 @@
+//! Record and replay the interactions with Canvas.
+//!
+//! A [`Recorder`] is a transport that sends the requests to Canvas and records every request and
+//! response, after scrubbing the access token and personal information. The cassette file is
+//! written when the recorder is dropped, or with [`Recorder::save`].
+//! A [`Player`] is a transport that serves the responses of a cassette, without any network
+//! access. A request that is not in the cassette fails with an [`UnmatchedRequest`] error.
+//!
+//! # Example
+//!
+//! Record the interactions once, against a real Canvas server:
+//!
+//! ```no_run
+//! # use canvasapi::prelude::*;
+//! use canvasapi::cassette::{Recorder, Scrubber};
+//!
+//! let recorder = Recorder::new("tests/cassettes/students.json")
+//!     .scrubber(Scrubber::default().replace("canvas.university.edu", "canvas.test"));
+//! let canvas = CanvasInformation::builder("https://canvas.university.edu", "MY_TOKEN")
+//!     .transport(recorder.clone())
+//!     .build()
+//!     .unwrap();
+//!
+//! // Send the requests, and write the cassette.
+//! recorder.save().unwrap();
+//! ```
+//!
+//! And replay them in CI:
+//!
+//! ```no_run
+//! # use canvasapi::prelude::*;
+//! use canvasapi::cassette::Player;
+//!
+//! let canvas = CanvasInformation::builder("https://canvas.test", "FAKE_TOKEN")
+//!     .transport(Player::open("tests/cassettes/students.json").unwrap())
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use futures::future::BoxFuture;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+use crate::error::CanvasError;
+use crate::transport::{HttpRequest, HttpResponse, ReqwestTransport, Transport};
+
+/// The JSON fields of which the values are replaced by the default [`Scrubber`].
+const PERSONAL_FIELDS: &[&str] = &[
+    "name",
+    "sortable_name",
+    "short_name",
+    "display_name",
+    "user_name",
+    "email",
+    "login_id",
+    "sis_user_id",
+    "integration_id",
+    "avatar_url",
+    "avatar_image_url",
+    "bio",
+    "pronouns",
+];
+
+/// The response headers that are never recorded.
+const SECRET_HEADERS: &[&str] = &["set-cookie", "authorization"];
+
+/// The query parameters and JSON fields of which the values are never recorded.
+const SECRET_PARAMETERS: &[&str] = &[
+    "access_token",
+    "refresh_token",
+    "client_secret",
+    "code",
+    "verifier",
+];
+
+/// The text that replaces scrubbed values.
+const REDACTED: &str = "REDACTED";
+
+/// The recorded interactions, as stored in a cassette file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+/// A recorded request and its response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// A recorded request.
+///
+/// The headers of the request are not recorded, as they contain the access token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Body>,
+}
+
+/// A recorded response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Body,
+}
+
+/// A recorded body. Bodies that are not valid UTF-8, such as downloaded files, are stored as
+/// bytes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Body {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl From<&[u8]> for Body {
+    fn from(body: &[u8]) -> Self {
+        match std::str::from_utf8(body) {
+            Ok(text) => Body::Text(text.to_string()),
+            Err(_) => Body::Bytes(body.to_vec()),
+        }
+    }
+}
+
+impl RecordedResponse {
+    /// Restore the response.
+    pub(crate) fn to_response(&self) -> Result<HttpResponse, CanvasError> {
+        let invalid =
+            |what: &str| CanvasError::InvalidResponse(format!("Invalid {what} in the cassette"));
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            headers.append(
+                HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid("header"))?,
+                HeaderValue::from_str(value).map_err(|_| invalid("header"))?,
+            );
+        }
+
+        Ok(HttpResponse {
+            status: StatusCode::from_u16(self.status).map_err(|_| invalid("status"))?,
+            headers,
+            url: Url::parse(&self.url).map_err(|_| invalid("url"))?,
+            body: self.body.to_bytes(),
+        })
+    }
+}
+
+impl From<&HttpResponse> for RecordedResponse {
+    /// Record a response as is, without scrubbing.
+    fn from(response: &HttpResponse) -> Self {
+        Self {
+            status: response.status.as_u16(),
+            url: response.url.to_string(),
+            headers: response
+                .headers
+                .iter()
+                .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.into())))
+                .collect(),
+            body: Body::from(response.body.as_ref()),
+        }
+    }
+}
+
+impl Body {
+    fn to_bytes(&self) -> bytes::Bytes {
+        match self {
+            Body::Text(text) => text.clone().into(),
+            Body::Bytes(bytes) => bytes.clone().into(),
+        }
+    }
+}
+
+impl Cassette {
+    /// Read a cassette file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, CanvasError> {
+        let content = std::fs::read(path)?;
+        let deserializer = &mut serde_json::Deserializer::from_slice(&content);
+        serde_path_to_error::deserialize(deserializer)
+            .map_err(|error| CanvasError::deserialize(&content, error))
+    }
+
+    /// Write the cassette to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CanvasError> {
+        let content = serde_json::to_vec_pretty(self).map_err(CanvasError::Serialize)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// Removes the access token and personal information from the recorded interactions.
+///
+/// The request headers and the `Set-Cookie` response header are never recorded, and the values of
+/// secret query parameters and JSON fields, such as `access_token` and `refresh_token`, are always
+/// replaced. By default, the string values of JSON fields with personal information, such as
+/// `name`, `email` and `login_id`, are replaced as well, and so are the SIS ids in the urls and
+/// headers, such as `sis_user_id:s123456` in a path or in the `as_user_id` query parameter.
+#[derive(Debug, Clone)]
+pub struct Scrubber {
+    fields: HashSet<String>,
+    sis_ids: bool,
+    replacements: Vec<(String, String)>,
+}
+
+impl Default for Scrubber {
+    fn default() -> Self {
+        Self {
+            fields: PERSONAL_FIELDS
+                .iter()
+                .map(|field| field.to_string())
+                .collect(),
+            sis_ids: true,
+            replacements: vec![],
+        }
+    }
+}
+
+impl Scrubber {
+    /// A scrubber that only removes the access token, and keeps all the other information.
+    pub fn none() -> Self {
+        Self {
+            fields: HashSet::new(),
+            sis_ids: false,
+            replacements: vec![],
+        }
+    }
+
+    /// Also replace the string values of this JSON field.
+    pub fn field(mut self, field: impl Into<String>) -> Self {
+        self.fields.insert(field.into());
+        self
+    }
+
+    /// Replace a text in the urls, headers and bodies, for example the host of the Canvas server
+    /// or the name of a student.
+    pub fn replace(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.replacements.push((from.into(), to.into()));
+        self
+    }
+
+    fn scrub_text(&self, text: &str) -> String {
+        let text = self
+            .replacements
+            .iter()
+            .fold(text.to_string(), |text, (from, to)| text.replace(from, to));
+
+        if self.sis_ids {
+            scrub_sis_ids(&text)
+        } else {
+            text
+        }
+    }
+
+    fn scrub_url(&self, url: &Url) -> String {
+        let mut url = url.clone();
+
+        if url
+            .query_pairs()
+            .any(|(name, _)| is_secret_parameter(&name))
+        {
+            let query: Vec<(String, String)> = url
+                .query_pairs()
+                .map(|(name, value)| {
+                    if is_secret_parameter(&name) {
+                        (name.into_owned(), REDACTED.to_string())
+                    } else {
+                        (name.into_owned(), value.into_owned())
+                    }
+                })
+                .collect();
+            url.query_pairs_mut().clear().extend_pairs(query);
+        }
+
+        self.scrub_text(url.as_str())
+    }
+
+    fn scrub_body(&self, body: &[u8]) -> Body {
+        match serde_json::from_slice::<Value>(body) {
+            Ok(mut value) => {
+                self.scrub_value(&mut value);
+                Body::Text(self.scrub_text(&value.to_string()))
+            }
+            _ => match Body::from(body) {
+                Body::Text(text) => Body::Text(self.scrub_text(&text)),
+                body => body,
+            },
+        }
+    }
+
+    fn scrub_value(&self, value: &mut Value) {
+        match value {
+            Value::Object(fields) => {
+                for (name, value) in fields {
+                    match value {
+                        Value::String(text)
+                            if self.fields.contains(name) || is_secret_parameter(name) =>
+                        {
+                            *text = REDACTED.to_string()
+                        }
+                        value => self.scrub_value(value),
+                    }
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(|value| self.scrub_value(value)),
+            _ => {}
+        }
+    }
+
+    fn interaction(&self, request: &HttpRequest, response: &HttpResponse) -> Interaction {
+        Interaction {
+            request: RecordedRequest {
+                method: request.method.to_string(),
+                url: self.scrub_url(&request.url),
+                body: request.body.as_ref().map(|body| self.scrub_body(body)),
+            },
+            response: RecordedResponse {
+                status: response.status.as_u16(),
+                url: self.scrub_url(&response.url),
+                headers: response
+                    .headers
+                    .iter()
+                    .filter(|(name, _)| !SECRET_HEADERS.contains(&name.as_str()))
+                    .filter_map(|(name, value)| {
+                        Some((name.to_string(), self.scrub_text(value.to_str().ok()?)))
+                    })
+                    .collect(),
+                body: self.scrub_body(&response.body),
+            },
+        }
+    }
+}
+
+fn is_secret_parameter(name: &str) -> bool {
+    SECRET_PARAMETERS.contains(&name)
+}
+
+/// Replace the values of the SIS ids in a url or header, such as `sis_user_id:s123456` in a path,
+/// or `sis_user_id%3As123456` in a query.
+fn scrub_sis_ids(text: &str) -> String {
+    let mut scrubbed = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("sis_") {
+        let (before, after) = rest.split_at(start);
+        let end = after
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(after.len());
+        let (name, after) = after.split_at(end);
+        scrubbed.push_str(before);
+        scrubbed.push_str(name);
+        rest = after;
+
+        let separator = [":", "%3A", "%3a"]
+            .into_iter()
+            .find(|separator| after.starts_with(separator));
+        if let Some(separator) = separator.filter(|_| name.ends_with("_id")) {
+            let value = &after[separator.len()..];
+            let end = value
+                .find(|c: char| "/?&#;,<>\"".contains(c) || c.is_whitespace())
+                .unwrap_or(value.len());
+            scrubbed.push_str(separator);
+            scrubbed.push_str(REDACTED);
+            rest = &value[end..];
+        }
+    }
+
+    scrubbed.push_str(rest);
+    scrubbed
+}
+
+/// A transport that records the interactions with Canvas to a cassette file.
+///
+/// The interactions are kept in memory, and the cassette file is written when the last clone of
+/// the recorder is dropped, or with [`save`](Recorder::save). An existing file is overwritten.
+/// Keep a clone of the recorder to save the cassette while the transport is still in use.
+#[derive(Clone)]
+pub struct Recorder {
+    transport: Arc<dyn Transport>,
+    scrubber: Scrubber,
+    recording: Arc<Recording>,
+}
+
+/// The interactions of a recorder, and the file to which they are written.
+struct Recording {
+    path: PathBuf,
+    cassette: Mutex<Cassette>,
+    /// The number of interactions that were written to the file.
+    saved: Mutex<usize>,
+}
+
+impl Recording {
+    fn save(&self) -> Result<(), CanvasError> {
+        // The saved lock orders the writes, while the cassette lock is only held to serialize it.
+        let mut saved = self.saved.lock().unwrap();
+        let (content, interactions) = {
+            let cassette = self.cassette.lock().unwrap();
+            let content = serde_json::to_vec_pretty(&*cassette).map_err(CanvasError::Serialize)?;
+            (content, cassette.interactions.len())
+        };
+
+        std::fs::write(&self.path, content)?;
+        *saved = interactions;
+        Ok(())
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        let saved = *self.saved.get_mut().unwrap();
+        if self.cassette.get_mut().unwrap().interactions.len() != saved {
+            let _ = self.save();
+        }
+    }
+}
+
+impl Recorder {
+    /// Record the interactions to the file at `path`, using the default `reqwest` transport and
+    /// the default [`Scrubber`].
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            transport: Arc::new(ReqwestTransport::default()),
+            scrubber: Scrubber::default(),
+            recording: Arc::new(Recording {
+                path: path.into(),
+                cassette: Mutex::new(Cassette::default()),
+                saved: Mutex::new(0),
+            }),
+        }
+    }
+
+    /// Write the interactions that are recorded so far to the cassette file.
+    pub fn save(&self) -> Result<(), CanvasError> {
+        self.recording.save()
+    }
+
+    /// Send the requests with another transport.
+    pub fn transport(mut self, transport: impl Transport) -> Self {
+        self.transport = Arc::new(transport);
+        self
+    }
+
+    /// Use another scrubber for removing personal information.
+    pub fn scrubber(mut self, scrubber: Scrubber) -> Self {
+        self.scrubber = scrubber;
+        self
+    }
+}
+
+impl Transport for Recorder {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, CanvasError>> {
+        Box::pin(async move {
+            let response = self.transport.send(request.clone()).await?;
+
+            let interaction = self.scrubber.interaction(&request, &response);
+            self.recording
+                .cassette
+                .lock()
+                .unwrap()
+                .interactions
+                .push(interaction);
+
+            Ok(response)
+        })
+    }
+}
+
+/// A transport that serves the responses of a cassette, without network access.
+///
+/// A request matches an interaction when the method, path, query and body are equal.
+/// The scheme and host are ignored, such that the cassette can be replayed with another base URL.
+/// When the same request is recorded more than once, the responses are served in the recorded
+/// order, and the last one is repeated.
+pub struct Player {
+    interactions: Vec<Interaction>,
+    used: Mutex<Vec<bool>>,
+    scrubber: Scrubber,
+}
+
+impl Player {
+    /// Replay a cassette.
+    pub fn new(cassette: Cassette) -> Self {
+        Self {
+            used: Mutex::new(vec![false; cassette.interactions.len()]),
+            interactions: cassette.interactions,
+            scrubber: Scrubber::default(),
+        }
+    }
+
+    /// Replay the cassette in the file at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, CanvasError> {
+        Ok(Self::new(Cassette::open(path)?))
+    }
+
+    /// Scrub the requests before matching them with the recorded requests.
+    ///
+    /// This must be the scrubber with which the cassette was recorded, such that the bodies of the
+    /// requests match. By default, the default [`Scrubber`] is used.
+    pub fn scrubber(mut self, scrubber: Scrubber) -> Self {
+        self.scrubber = scrubber;
+        self
+    }
+
+    fn find(&self, request: &HttpRequest) -> Option<&Interaction> {
+        let key = self.request_key(
+            request.method.as_str(),
+            &self.scrubber.scrub_url(&request.url),
+        );
+        let body = request
+            .body
+            .as_ref()
+            .map(|body| self.scrubber.scrub_body(body).to_bytes());
+
+        let mut used = self.used.lock().unwrap();
+        let matches: Vec<usize> = self
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, interaction)| {
+                key.is_some()
+                    && self.request_key(&interaction.request.method, &interaction.request.url)
+                        == key
+                    && interaction.request.body.as_ref().map(Body::to_bytes) == body
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        let index = matches
+            .iter()
+            .copied()
+            .find(|index| !used[*index])
+            .or_else(|| matches.last().copied())?;
+        used[index] = true;
+
+        Some(&self.interactions[index])
+    }
+
+    /// The method, path and query of a request.
+    fn request_key(&self, method: &str, url: &str) -> Option<(String, String, Option<String>)> {
+        let url = Url::parse(url).ok()?;
+        Some((
+            method.to_uppercase(),
+            url.path().to_string(),
+            url.query().map(str::to_string),
+        ))
+    }
+}
+
+impl Transport for Player {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, CanvasError>> {
+        Box::pin(async move {
+            let interaction = self.find(&request).ok_or_else(|| {
+                CanvasError::Transport(Box::new(UnmatchedRequest {
+                    method: request.method.clone(),
+                    url: request.url.to_string(),
+                }))
+            })?;
+            interaction.response.to_response()
+        })
+    }
+}
+
+/// A request that is not recorded in the cassette that is replayed.
+#[derive(Debug, thiserror::Error)]
+#[error("no recorded interaction for {method} {url}")]
+pub struct UnmatchedRequest {
+    pub method: Method,
+    pub url: String,
+}
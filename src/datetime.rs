@@ -0,0 +1,43 @@
+//! The timestamps of the models.
+//!
+//! Canvas returns timestamps in ISO 8601, for example `2024-05-01T21:59:59Z`. Without the `chrono`
+//! feature, the timestamp fields of the models keep the string that Canvas returned. With the
+//! `chrono` feature, they are deserialized into a `chrono::DateTime<Utc>`, and a timestamp that is
+//! not valid fails the deserialization of the model. The models then also get helpers such as
+//! `Assignment::is_overdue`.
+//!
+//! # Example
+//!
+//! ```
+//! # use canvasapi::prelude::*;
+//! # #[cfg(feature = "chrono")]
+//! # {
+//! let assignment: Assignment =
+//!     serde_json::from_str(r#"{"due_at": "2024-05-01T23:59:59+02:00"}"#).unwrap();
+//!
+//! let due_at = chrono::DateTime::parse_from_rfc3339("2024-05-01T21:59:59Z").unwrap();
+//! assert_eq!(assignment.due_at, Some(due_at.into()));
+//! # }
+//! ```
+
+/// A timestamp of Canvas, in UTC.
+#[cfg(feature = "chrono")]
+pub type Timestamp = chrono::DateTime<chrono::Utc>;
+
+/// A timestamp of Canvas, as the string that Canvas returned.
+///
+/// With the `chrono` feature, this is a `chrono::DateTime<Utc>`.
+#[cfg(not(feature = "chrono"))]
+pub type Timestamp = String;
+
+/// Check whether an object that is available between `unlock_at` and `lock_at` is locked at the
+/// given time.
+#[cfg(feature = "chrono")]
+pub(crate) fn is_locked_at(
+    unlock_at: Option<Timestamp>,
+    lock_at: Option<Timestamp>,
+    now: Timestamp,
+) -> bool {
+    unlock_at.is_some_and(|unlock_at| now < unlock_at)
+        || lock_at.is_some_and(|lock_at| now >= lock_at)
+}
@@ -0,0 +1,264 @@
+//! Typed endpoints of the Canvas API.
+//!
+//! An [`Endpoint`] describes a request: the method, the path, the query parameters, the body and
+//! the type of the response. A [`Query`] sends an endpoint that returns a single object, and a
+//! [`PagedQuery`] an endpoint that returns a list of objects over multiple pages.
+//!
+//! The requests of this crate are endpoints as well, such that endpoints that are not (yet)
+//! supported can be defined outside of the crate and used in the same way.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use canvasapi::prelude::*;
+//! # async fn test(canvas: &CanvasInformation) -> Result<(), CanvasError> {
+//! use canvasapi::endpoint::{Endpoint, Query};
+//! use serde::Deserialize;
+//!
+//! /// The activity stream summary of the current user.
+//! struct ActivityStreamSummary;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct ActivitySummary {
+//!     #[serde(rename = "type")]
+//!     activity_type: String,
+//!     unread_count: usize,
+//!     count: usize,
+//! }
+//!
+//! impl Endpoint for ActivityStreamSummary {
+//!     type Output = Vec<ActivitySummary>;
+//!
+//!     fn path(&self) -> String {
+//!         "users/self/activity_stream/summary".to_string()
+//!     }
+//! }
+//!
+//! let summary = Query::new(ActivityStreamSummary).fetch(canvas).await?.inner();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Declaring endpoints
+//!
+//! The [`canvas_endpoint`] attribute implements [`Endpoint`] for a struct with the parameters of
+//! the request. The fields in the path are formatted into it, and the other fields are sent as
+//! query parameters.
+//!
+//! ```no_run
+//! # use canvasapi::prelude::*;
+//! # async fn test(canvas: &CanvasInformation) -> Result<(), CanvasError> {
+//! use canvasapi::endpoint::canvas_endpoint;
+//!
+//! /// List the announcements of a course.
+//! #[canvas_endpoint(GET, "announcements", paged, output = serde_json::Value)]
+//! struct GetAnnouncements {
+//!     #[query(name = "context_codes[]")]
+//!     context_codes: Vec<String>,
+//!     active_only: Option<bool>,
+//! }
+//!
+//! let announcements = GetAnnouncements {
+//!     context_codes: vec!["course_1".to_string()],
+//!     active_only: Some(true),
+//! }
+//! .fetch(canvas)
+//! .await?
+//! .inner();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::marker::PhantomData;
+
+use crate::error::CanvasError;
+use crate::parameters::RequestParameter;
+
+pub use canvasapi_derive::canvas_endpoint;
+pub use reqwest::Method;
+
+#[cfg(feature = "blocking")]
+pub use crate::requests::PagedObjectIter;
+pub use crate::requests::{GetObjectRequestMap, GetObjectResponse, PagedQuery, Query};
+
+/// A request to the REST API of Canvas.
+///
+/// Only the path and the output are required: by default, the endpoint is a get request without
+/// parameters and without body.
+pub trait Endpoint {
+    /// The type of the response.
+    ///
+    /// For endpoints that are sent with a [`PagedQuery`], this is the type of a single object of
+    /// the pages.
+    type Output: serde::de::DeserializeOwned;
+
+    /// The method of the request.
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    /// The path of the request, relative to `/api/v1/`, for example `courses/1/users`.
+    fn path(&self) -> String;
+
+    /// The query parameters of the request.
+    ///
+    /// The parameters that are added to the [`Query`] or [`PagedQuery`] are sent after these.
+    fn parameters(&self) -> Vec<RequestParameter> {
+        vec![]
+    }
+
+    /// The JSON body of the request.
+    fn body(&self) -> Result<Option<serde_json::Value>, CanvasError> {
+        Ok(None)
+    }
+
+    /// The path before the arguments are filled in, for example `courses/{id}/users`.
+    ///
+    /// It is used in the tracing spans instead of the path, when given.
+    fn template(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+/// An endpoint with a path that is formatted beforehand.
+///
+/// This is the endpoint of the requests of the models of this crate.
+pub struct RawEndpoint<Output> {
+    method: Method,
+    path: String,
+    template: Option<&'static str>,
+    parameters: Vec<RequestParameter>,
+    body: Option<serde_json::Value>,
+    output: PhantomData<fn() -> Output>,
+}
+
+impl<Output> RawEndpoint<Output> {
+    /// Create an endpoint with the given method and path, relative to `/api/v1/`.
+    pub fn new(method: Method, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            template: None,
+            parameters: vec![],
+            body: None,
+            output: PhantomData,
+        }
+    }
+
+    /// Create a get endpoint with the given path, relative to `/api/v1/`.
+    pub fn get(path: impl Into<String>) -> Self {
+        Self::new(Method::GET, path)
+    }
+
+    /// Set the path before the arguments are filled in, which is used in the tracing spans.
+    pub fn template(mut self, template: &'static str) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    /// Add a query parameter, which can be any parameter. Array parameters are added once for
+    /// every value.
+    pub fn parameter(mut self, parameter: impl Into<RequestParameter>) -> Self {
+        self.parameters.push(parameter.into());
+        self
+    }
+
+    /// Set the JSON body that is sent with the request.
+    pub fn body(mut self, body: impl serde::Serialize) -> Result<Self, CanvasError> {
+        self.body = Some(serde_json::to_value(body).map_err(CanvasError::Serialize)?);
+        Ok(self)
+    }
+}
+
+impl<Output> Endpoint for RawEndpoint<Output>
+where
+    Output: serde::de::DeserializeOwned,
+{
+    type Output = Output;
+
+    fn method(&self) -> Method {
+        self.method.clone()
+    }
+
+    fn path(&self) -> String {
+        self.path.clone()
+    }
+
+    fn parameters(&self) -> Vec<RequestParameter> {
+        self.parameters.clone()
+    }
+
+    fn body(&self) -> Result<Option<serde_json::Value>, CanvasError> {
+        Ok(self.body.clone())
+    }
+
+    fn template(&self) -> Option<&'static str> {
+        self.template
+    }
+}
+
+/// A value that is formatted into the path of a request.
+///
+/// The characters that are not allowed in a segment of a path are percent-encoded, such that, for
+/// example, a SIS id with a slash or a space does not change the path.
+#[doc(hidden)]
+pub struct PathSegment<'a, T: ?Sized>(pub &'a T);
+
+impl<T: std::fmt::Display + ?Sized> std::fmt::Display for PathSegment<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        use std::fmt::Write;
+
+        for byte in self.0.to_string().bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' => f.write_char(byte as char)?,
+                b'-' | b'.' | b'_' | b'~' | b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*'
+                | b'+' | b',' | b';' | b'=' | b':' | b'@' => f.write_char(byte as char)?,
+                _ => write!(f, "%{byte:02X}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The JSON body of an endpoint, built from the fields with the `#[body]` attribute of
+/// [`canvas_endpoint`].
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Body(serde_json::Map<String, serde_json::Value>);
+
+#[doc(hidden)]
+pub type BodyResult = Result<Option<serde_json::Value>, CanvasError>;
+
+impl Body {
+    /// Add a field to the body.
+    pub fn field(mut self, name: &str, value: &impl serde::Serialize) -> Result<Self, CanvasError> {
+        let value = serde_json::to_value(value).map_err(CanvasError::Serialize)?;
+        self.0.insert(name.to_string(), value);
+        Ok(self)
+    }
+
+    /// The body as a JSON object.
+    pub fn finish(self) -> BodyResult {
+        Ok(Some(serde_json::Value::Object(self.0)))
+    }
+}
+
+/// Emit the items only when the `blocking` feature of this crate is enabled.
+///
+/// This is used by the code that [`canvas_endpoint`] generates, as the features of the crate that
+/// uses the macro are not the features of this crate.
+#[doc(hidden)]
+#[cfg(feature = "blocking")]
+#[macro_export]
+macro_rules! __blocking {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "blocking"))]
+#[macro_export]
+macro_rules! __blocking {
+    ($($item:item)*) => {};
+}
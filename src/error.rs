@@ -0,0 +1,250 @@
+//! Errors that are returned by the requests to Canvas.
+//!
+//! # Example
+//!
+//! ```
+//! # use canvasapi::prelude::*;
+//! fn describe(error: &CanvasError) -> &'static str {
+//!     match error {
+//!         CanvasError::Unauthorized(_) => "check the access token",
+//!         CanvasError::NotFound(_) => "the object does not exist",
+//!         CanvasError::RateLimited(_) => "slow down",
+//!         _ => "something else went wrong",
+//!     }
+//! }
+//! ```
+
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::transport::HttpResponse;
+
+/// The maximum number of characters of a response body that is kept in an error.
+const SNIPPET_LENGTH: usize = 200;
+
+/// An error that occurred while making a request to Canvas.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum CanvasError {
+    /// The access token is missing, invalid or expired (401).
+    #[error("unauthorized: {0}")]
+    Unauthorized(ApiError),
+    /// The access token does not have permission for the request (403).
+    #[error("forbidden: {0}")]
+    Forbidden(ApiError),
+    /// The requested object does not exist (404).
+    #[error("not found: {0}")]
+    NotFound(ApiError),
+    /// The rate limit quota of the access token is used up (403 or 429).
+    #[error("rate limited: {0}")]
+    RateLimited(ApiError),
+    /// Canvas failed to handle the request (5xx).
+    #[error("server error: {0}")]
+    Server(ApiError),
+    /// Canvas answered with another unsuccessful status, for example an invalid parameter.
+    #[error("request failed: {0}")]
+    Status(ApiError),
+    /// The GraphQL API returned errors.
+    #[error("graphql error: {}", graphql_messages(.0))]
+    GraphQL(Vec<GraphQLError>),
+    /// The response body could not be deserialized.
+    #[error("failed to deserialize `{path}`: {source} (body: {snippet})")]
+    Deserialize {
+        /// The path in the JSON document where deserialization failed.
+        path: String,
+        /// The start of the response body.
+        snippet: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    /// The body of a request could not be serialized.
+    #[error("failed to serialize the request body: {0}")]
+    Serialize(#[source] serde_json::Error),
+    /// A field that is needed to make the request is not set.
+    #[error("field `{0}` missing")]
+    MissingField(String),
+    /// The response of Canvas is not as expected.
+    #[error("invalid response: {0}")]
+    InvalidResponse(String),
+    /// The request could not be created.
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+    /// The configuration of the Canvas information is invalid.
+    #[error("invalid configuration: {0}")]
+    Config(String),
+    /// The request could not be sent, or the response could not be received.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// A custom transport failed to send the request or to receive the response.
+    #[error("transport error: {0}")]
+    Transport(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// Reading or writing a file failed.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+impl CanvasError {
+    pub(crate) fn missing_field(field: &str) -> Self {
+        Self::MissingField(field.to_string())
+    }
+
+    /// The status of the response, when the error is caused by an unsuccessful response.
+    pub fn status(&self) -> Option<StatusCode> {
+        self.api_error().map(|error| error.status)
+    }
+
+    /// The error that Canvas returned, when the error is caused by an unsuccessful response.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Self::Unauthorized(error)
+            | Self::Forbidden(error)
+            | Self::NotFound(error)
+            | Self::RateLimited(error)
+            | Self::Server(error)
+            | Self::Status(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Create the error for an unsuccessful response.
+    pub(crate) fn from_response(resp: &HttpResponse) -> Self {
+        let error = ApiError {
+            status: resp.status,
+            messages: error_messages(&resp.body),
+        };
+
+        match resp.status {
+            _ if resp.is_rate_limited() => Self::RateLimited(error),
+            StatusCode::UNAUTHORIZED => Self::Unauthorized(error),
+            StatusCode::FORBIDDEN => Self::Forbidden(error),
+            StatusCode::NOT_FOUND => Self::NotFound(error),
+            status if status.is_server_error() => Self::Server(error),
+            _ => Self::Status(error),
+        }
+    }
+
+    /// Create the error for a response body that could not be deserialized.
+    pub(crate) fn deserialize(
+        body: &[u8],
+        error: serde_path_to_error::Error<serde_json::Error>,
+    ) -> Self {
+        Self::Deserialize {
+            path: error.path().to_string(),
+            snippet: String::from_utf8_lossy(body)
+                .chars()
+                .take(SNIPPET_LENGTH)
+                .collect(),
+            source: error.into_inner(),
+        }
+    }
+}
+
+/// The error that Canvas returned with an unsuccessful response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    /// The status of the response.
+    pub status: StatusCode,
+    /// The messages of the `errors` in the response body.
+    pub messages: Vec<String>,
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.status)?;
+
+        if !self.messages.is_empty() {
+            write!(f, " ({})", self.messages.join("; "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An error in the response of the GraphQL API.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct GraphQLError {
+    /// The description of the error.
+    pub message: String,
+    /// The locations in the query to which the error applies.
+    #[serde(default)]
+    pub locations: Vec<GraphQLLocation>,
+    /// The path of the field in the response to which the error applies, with field names and
+    /// list indices.
+    #[serde(default)]
+    pub path: Vec<Value>,
+}
+
+/// A location in a GraphQL query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub struct GraphQLLocation {
+    pub line: usize,
+    pub column: usize,
+}
+
+fn graphql_messages(errors: &[GraphQLError]) -> String {
+    errors
+        .iter()
+        .map(|error| error.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Collect the error messages from a Canvas error response.
+///
+/// Canvas returns the errors in different forms, for example:
+/// - `{"errors": [{"message": "The specified resource does not exist."}]}`
+/// - `{"errors": {"name": [{"attribute": "name", "message": "too long"}]}}`
+/// - `{"message": "Invalid access token."}`
+/// - `{"error": "invalid_grant", "error_description": "refresh_token not found"}`
+fn error_messages(body: &[u8]) -> Vec<String> {
+    let value: Value = match serde_json::from_slice(body) {
+        Ok(value) => value,
+        Err(_) => return vec![],
+    };
+
+    let mut messages = vec![];
+
+    match value.get("errors") {
+        Some(Value::Array(errors)) => {
+            messages.extend(errors.iter().filter_map(|error| error_message(error, None)));
+        }
+        Some(Value::Object(fields)) => {
+            for (field, errors) in fields {
+                match errors {
+                    Value::Array(errors) => messages.extend(
+                        errors
+                            .iter()
+                            .filter_map(|error| error_message(error, Some(field))),
+                    ),
+                    error => messages.extend(error_message(error, Some(field))),
+                }
+            }
+        }
+        Some(error) => messages.extend(error_message(error, None)),
+        None => {}
+    }
+
+    if let Some(Value::String(message)) = value.get("message") {
+        messages.push(message.clone());
+    }
+
+    // The OAuth2 endpoints return the errors in the form of RFC 6749.
+    if let Some(Value::String(description)) = value.get("error_description") {
+        messages.push(description.clone());
+    }
+
+    messages
+}
+
+fn error_message(error: &Value, field: Option<&str>) -> Option<String> {
+    let message = match error {
+        Value::String(message) => message.clone(),
+        Value::Object(error) => error.get("message")?.as_str()?.to_string(),
+        _ => return None,
+    };
+
+    Some(match field {
+        Some(field) => format!("{field}: {message}"),
+        None => message,
+    })
+}
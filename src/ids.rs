@@ -0,0 +1,310 @@
+//! Identifiers of the objects of Canvas.
+//!
+//! Every kind of object has its own identifier type, such that, for example, the id of a user can
+//! not be passed where the id of a course is expected. The identifiers serialize as the number
+//! that Canvas uses, and are also parsed from the string-encoded ids that Canvas returns when the
+//! `Accept: application/json+canvas-string-ids` header is sent.
+//!
+//! Courses and users can also be referred to by their id in the SIS (the student information
+//! system of the institution), which Canvas accepts in the place of the Canvas id in the form
+//! `sis_course_id:<id>` and `sis_user_id:<id>`.
+//!
+//! # Example
+//!
+//! ```
+//! # use canvasapi::prelude::*;
+//! let course = Canvas::get_course(CourseId::sis("2024-CS101")).unwrap();
+//! assert_eq!(course.endpoint().path(), "courses/sis_course_id:2024-CS101");
+//!
+//! let id: AssignmentId = serde_json::from_str(r#""42""#).unwrap();
+//! assert_eq!(id, 42);
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The error that is returned when an identifier can not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid {kind}: `{value}`")]
+pub struct ParseIdError {
+    kind: &'static str,
+    value: String,
+}
+
+impl ParseIdError {
+    fn new(kind: &'static str, value: &str) -> Self {
+        Self {
+            kind,
+            value: value.to_string(),
+        }
+    }
+}
+
+/// The visitor of the identifiers, which accepts a number or a string.
+struct IdVisitor<T>(std::marker::PhantomData<T>, &'static str);
+
+impl<'de, T> serde::de::Visitor<'de> for IdVisitor<T>
+where
+    T: From<u64> + FromStr<Err = ParseIdError>,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a {}", self.1)
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<T, E> {
+        Ok(T::from(value))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<T, E> {
+        u64::try_from(value)
+            .map(T::from)
+            .map_err(|_| E::custom(ParseIdError::new(self.1, &value.to_string())))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<T, E> {
+        value.parse().map_err(E::custom)
+    }
+}
+
+macro_rules! canvas_id {
+    (
+        $(#[$outer:meta])*
+        $name:ident => $kind:expr
+    ) => {
+        $(#[$outer])*
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name(pub u64);
+
+        impl $name {
+            /// Return the Canvas id as a number.
+            pub fn get(self) -> u64 {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseIdError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse()
+                    .map(Self)
+                    .map_err(|_| ParseIdError::new($kind, s))
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(id: u64) -> Self {
+                Self(id)
+            }
+        }
+
+        impl PartialEq<u64> for $name {
+            fn eq(&self, other: &u64) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_u64(self.0)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_any(IdVisitor(std::marker::PhantomData, $kind))
+            }
+        }
+    };
+
+    (
+        $(#[$outer:meta])*
+        $name:ident => $kind:expr, sis = $prefix:expr
+    ) => {
+        $(#[$outer])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            /// The Canvas id.
+            Id(u64),
+            /// The SIS id, sent as
+            #[doc = concat!("`", $prefix, ":<id>`.")]
+            Sis(String),
+        }
+
+        impl $name {
+            /// Refer to the object by its SIS id.
+            pub fn sis(id: impl Into<String>) -> Self {
+                Self::Sis(id.into())
+            }
+
+            /// Return the Canvas id as a number, or `None` for a SIS id.
+            pub fn get(&self) -> Option<u64> {
+                match self {
+                    Self::Id(id) => Some(*id),
+                    Self::Sis(_) => None,
+                }
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::Id(0)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Self::Id(id) => id.fmt(f),
+                    Self::Sis(id) => write!(f, "{}:{id}", $prefix),
+                }
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseIdError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                if let Some(id) = s.strip_prefix(concat!($prefix, ":")) {
+                    return Ok(Self::Sis(id.to_string()));
+                }
+                s.parse()
+                    .map(Self::Id)
+                    .map_err(|_| ParseIdError::new($kind, s))
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(id: u64) -> Self {
+                Self::Id(id)
+            }
+        }
+
+        impl From<&$name> for $name {
+            fn from(id: &$name) -> Self {
+                id.clone()
+            }
+        }
+
+        impl PartialEq<u64> for $name {
+            fn eq(&self, other: &u64) -> bool {
+                self.get() == Some(*other)
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self {
+                    Self::Id(id) => serializer.serialize_u64(*id),
+                    Self::Sis(_) => serializer.collect_str(self),
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_any(IdVisitor(std::marker::PhantomData, $kind))
+            }
+        }
+    };
+}
+
+canvas_id! {
+    /// The id of a course, which can also be its SIS id.
+    CourseId => "course id", sis = "sis_course_id"
+}
+
+canvas_id! {
+    /// The id of a user, which can also be its SIS id.
+    UserId => "user id", sis = "sis_user_id"
+}
+
+canvas_id! {
+    /// The id of an account, which can also be its SIS id.
+    AccountId => "account id", sis = "sis_account_id"
+}
+
+canvas_id! {
+    /// The id of an assignment.
+    AssignmentId => "assignment id"
+}
+
+canvas_id! {
+    /// The id of an assignment group.
+    AssignmentGroupId => "assignment group id"
+}
+
+canvas_id! {
+    /// The id of a file.
+    FileId => "file id"
+}
+
+canvas_id! {
+    /// The id of a folder.
+    FolderId => "folder id"
+}
+
+canvas_id! {
+    /// The id of a submission.
+    SubmissionId => "submission id"
+}
+
+canvas_id! {
+    /// The id of a quiz.
+    QuizId => "quiz id"
+}
+
+canvas_id! {
+    /// The id of an outcome.
+    OutcomeId => "outcome id"
+}
+
+canvas_id! {
+    /// The id of a group.
+    GroupId => "group id"
+}
+
+canvas_id! {
+    /// The id of a group category.
+    GroupCategoryId => "group category id"
+}
+
+canvas_id! {
+    /// The id of an enrollment term.
+    EnrollmentTermId => "enrollment term id"
+}
+
+canvas_id! {
+    /// The id of a grading standard.
+    GradingStandardId => "grading standard id"
+}
+
+canvas_id! {
+    /// The id of a section of a course.
+    SectionId => "section id"
+}
+
+canvas_id! {
+    /// The id of an override of the dates of an assignment.
+    AssignmentOverrideId => "assignment override id"
+}
+
+canvas_id! {
+    /// The id of the set of students of an assignment override, which is a section or a group
+    /// depending on the `set_type`.
+    OverrideSetId => "override set id"
+}
+
+canvas_id! {
+    /// The id of the context of an object, which is a course or an account depending on the
+    /// `context_type`.
+    ContextId => "context id"
+}
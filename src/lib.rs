@@ -3,12 +3,21 @@
 //! [here](https://canvas.instructure.com/doc/api/).
 //!
 //! This client that is used for making the calls is [reqwest](https://crates.io/crates/reqwest).
-//! The API calls are by default asynchronous.
-//! The [Tokio](https://crates.io/crates/tokio) runtime is used for the tests.
+//! The API calls are asynchronous, and need a [Tokio](https://crates.io/crates/tokio) runtime.
+//! With the `blocking` feature, every request also has a blocking variant, such as
+//! `fetch_blocking`, which can be used together with the asynchronous API.
 //!
 //! # Feature flags
 //!
-//! - `blocking`: enables blocking requests.
+//! - `blocking`: enables the blocking variants of the requests, such as `fetch_blocking`.
+//!   The blocking requests are executed on an internal runtime and must not be made from within an
+//!   asynchronous runtime.
+//! - `tracing`: instruments the requests with [tracing](https://crates.io/crates/tracing) spans
+//!   and events, with the method, path, page, status, latency and rate limit of every request.
+//!   The access token is never recorded.
+//! - `chrono`: deserializes the timestamps of the models, such as `Assignment::due_at`, into
+//!   [chrono](https://crates.io/crates/chrono) datetimes instead of strings, and adds helpers such
+//!   as `Assignment::is_overdue`. See the [`datetime`] module.
 //! - `devel`: enables functions that are still in development.
 //!
 //! # Quickstart
@@ -18,7 +27,7 @@
 //! Using this information, the correct request URL's are created and, with a valid API token,
 //! executed.
 //!
-//! ```
+//! ```no_run
 //! # use canvasapi::prelude::*;
 //! # tokio_test::block_on(async {
 //! # dotenv::dotenv().ok();
@@ -30,9 +39,15 @@
 //! # });
 //! ```
 //!
+//! # Errors
+//!
+//! All requests return a [`CanvasError`](error::CanvasError) when they fail.
+//! Unsuccessful responses are mapped to a variant based on their status, for example
+//! `CanvasError::NotFound`, and contain the error messages returned by Canvas.
+//!
 //! # Contributing
 //!
-//! This crate only supports GET requests, and not all of them are implemented.
+//! Not all requests are implemented.
 //! Only the ones I use are implemented.
 //! Feel free to add more.
 //!
@@ -41,39 +56,53 @@
 //!    a struct. Define this struct in this library, under `models`, when it is not yet defined.
 //!    This information can be retrieved using the official Canvas API.
 //! 2. API request functions can are added in the implementation of the structs. These requests can
-//!    be methods or functions. To define a new request, use the `api_get!` macro. The following
-//!    example shows the usage of the macro.
+//!    be methods or functions. A request is declared with the `canvas_endpoint` attribute on a struct
+//!    with the arguments of the request, and the function returns its query. The following
+//!    example shows the usage of the attribute.
 //!
 //! ```ignore
-//! impl Course {
-//!     api_get! {
-//!         /// Get all courses from the current user.
-//!         courses():
-//!             "courses" =>
-//!             () -> () -> [Course]
-//!     }
+//! /// Return the assignment with the given id.
+//! #[canvas_endpoint(
+//!     GET,
+//!     "courses/{course_id}/assignments/{assignment_id}",
+//!     output = Assignment
+//! )]
+//! pub struct GetAssignment {
+//!     pub course_id: CourseId,
+//!     pub assignment_id: AssignmentId,
+//! }
 //!
-//!     api_get! {
-//!         /// Return the assignment with the given id.
-//!         get_assignment(self):
-//!             "courses/{id}/assignments/{assignment_id}" =>
-//!                 (id: self.id) -> (assignment_id: usize) -> Assignment
-//!     }
+//! /// Get only the students from the course.
+//! #[canvas_endpoint(
+//!     GET,
+//!     "courses/{course_id}/search_users",
+//!     paged,
+//!     output = User,
+//!     parameter = EnrollmentType::Student
+//! )]
+//! pub struct GetStudents {
+//!     pub course_id: CourseId,
+//! }
 //!
-//!     api_get! {
-//!         /// Get only the students from the course.
-//!         get_students(self):
-//!             "courses/{id}/search_users" =>
-//!                 (id: self.id) -> () -> [User]
-//!                 [EnrollmentType::Student]
+//! impl Course {
+//!     /// Return the assignment with the given id.
+//!     pub fn get_assignment(
+//!         &self,
+//!         assignment_id: impl Into<AssignmentId>,
+//!     ) -> Result<Query<GetAssignment>, CanvasError> {
+//!         Ok(GetAssignment {
+//!             course_id: self.id.clone(),
+//!             assignment_id: assignment_id.into(),
+//!         }
+//!         .query())
 //!     }
 //!
-//!     api_get! {
-//!         /// This is still in development.
-//!         function_in_developmen():
-//!             "test" =>
-//!                 () -> () -> Test
-//!             features = [(name = "devel", reason = "Function is not ready yet.")]
+//!     /// Get only the students from the course.
+//!     pub fn get_students(&self) -> Result<PagedQuery<GetStudents>, CanvasError> {
+//!         Ok(GetStudents {
+//!             course_id: self.id.clone(),
+//!         }
+//!         .query())
 //!     }
 //!
 //!     api_todo! {
@@ -83,30 +112,53 @@
 //! }
 //! ```
 //!
-//! First, you write the (optional) documentation string, for use in this generated
-//! documentation, followed by the function/method name.
-//! If it is a method, the `self` keyword is used between the parentheses.
-//! Next, you write the request url, without the base url and without `/api/v1/`.
+//! The attribute takes the method and the request url, without the base url and without `/api/v1/`.
 //! The full request is generated using the `CanvasInformation` struct.
-//! The request url can contain named parameters.
-//! The `self` arguments are defined in the first group and the function arguments are
-//! passed in the second group.
-//! Finaly, the return type of the request is defined by passing the returned struct.
-//! Square brackets are used when a `Vec` is returned by the API.
-//! Optionaly, requests parameters can be added.
+//! The fields of the struct between braces in the url are formatted into it, and the other fields
+//! are sent as query parameters, for which setters are generated on the struct and on its query.
+//! The `output` is the struct that is returned by the API, and `paged` is added when the API returns
+//! a list of them, over one or more pages.
+//! Optionaly, parameters that are always sent are added with `parameter`.
+//! Identifiers use the types of the [`ids`] module, such as `CourseId`, instead of integers.
+//! Requests that are still in development are put behind the `devel` feature with
+//! `#[cfg(feature = "devel")]` on the struct and the function.
+//!
+//! Requests that create, update or delete objects are defined in the same way, using the
+//! `api_post!`, `api_put!` and `api_delete!` macros.
+//! The body of these requests is set by the caller with the `body` method of the returned request.
 
 #![allow(dead_code, unused)]
 
+// The code generated by `canvas_endpoint` refers to this crate as `canvasapi`.
+extern crate self as canvasapi;
+
+#[macro_use]
+mod trace;
 #[macro_use]
 pub mod parameters;
 #[macro_use]
 mod requests;
 
+pub mod cache;
 pub mod canvas;
+pub mod cassette;
+pub mod datetime;
+pub mod endpoint;
+pub mod error;
+pub mod ids;
 pub mod models;
+pub mod oauth;
+pub mod pagination;
+pub mod transport;
 
 pub mod prelude {
-    pub use super::canvas::CanvasInformation;
+    pub use super::canvas::{CanvasInformation, CanvasInformationBuilder, Throttle};
+    pub use super::endpoint::{Endpoint, PagedQuery, Query};
+    pub use super::error::{ApiError, CanvasError, GraphQLError};
+    pub use super::ids::*;
     pub use super::models::prelude::*;
+    pub use super::pagination::{Links, Page};
     pub use super::parameters::*;
+    pub use super::requests::{GraphQLPagedRequest, GraphQLRequest, ResponseMetadata, RetryPolicy};
+    pub use super::transport::Transport;
 }
@@ -1,30 +1,33 @@
 //! Models for accessing information about assignments.
 
-use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::canvas::CanvasInformation;
+use crate::datetime::Timestamp;
+use crate::endpoint::{canvas_endpoint, PagedQuery, Query};
+use crate::error::CanvasError;
+use crate::ids::*;
 use crate::models::prelude::*;
 use crate::parameters::*;
 use crate::requests::*;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Assignment {
-    pub id: Option<usize>,
+    pub id: Option<AssignmentId>,
     pub name: Option<String>,
     pub description: Option<String>,
-    pub created_at: Option<String>,
-    pub updated_at: Option<String>,
-    pub due_at: Option<String>,
-    pub lock_at: Option<String>,
-    pub unlock_at: Option<String>,
+    pub created_at: Option<Timestamp>,
+    pub updated_at: Option<Timestamp>,
+    pub due_at: Option<Timestamp>,
+    pub lock_at: Option<Timestamp>,
+    pub unlock_at: Option<Timestamp>,
     pub has_overrides: Option<bool>,
     pub all_dates: Option<Vec<AllDates>>,
-    pub course_id: Option<usize>,
+    pub course_id: Option<CourseId>,
     pub html_url: Option<String>,
     pub submission_download_url: Option<String>,
-    pub assignment_group_id: Option<usize>,
+    pub assignment_group_id: Option<AssignmentGroupId>,
     pub due_date_required: Option<bool>,
     pub allowed_extensions: Option<Vec<String>>,
     pub max_name_length: Option<usize>,
@@ -36,9 +39,9 @@ pub struct Assignment {
     pub peer_reviews: Option<bool>,
     pub automatic_peer_reviews: Option<bool>,
     pub peer_review_count: Option<usize>,
-    pub peer_reviews_assign_at: Option<String>,
+    pub peer_reviews_assign_at: Option<Timestamp>,
     pub intra_group_peer_review: Option<bool>,
-    pub group_category_id: Option<usize>,
+    pub group_category_id: Option<GroupCategoryId>,
     pub needs_grading_count: Option<usize>,
     // pub needs_grading_count_by_section: usize,
     pub position: Option<usize>,
@@ -56,7 +59,7 @@ pub struct Assignment {
     pub locked_for_user: Option<bool>,
     pub lock_info: Option<LockInfo>,
     pub lock_explanation: Option<String>,
-    pub quiz_id: Option<usize>,
+    pub quiz_id: Option<QuizId>,
     pub anonymous_submissions: Option<bool>,
     // pub discussion_topic: usize,
     pub freeze_on_copy: Option<bool>,
@@ -66,12 +69,12 @@ pub struct Assignment {
     pub use_rubric_for_grading: Option<bool>,
     // pub rubric_settings: usize,
     // pub rubric: usize,
-    pub assignment_visibility: Option<Vec<usize>>,
+    pub assignment_visibility: Option<Vec<UserId>>,
     // pub overrides:usize,
     pub omit_from_final_grade: Option<bool>,
     pub moderated_grading: Option<bool>,
     pub grader_count: Option<usize>,
-    pub final_grader_id: Option<usize>,
+    pub final_grader_id: Option<UserId>,
     pub grader_comments_visible_to_graders: Option<bool>,
     pub graders_anonymous_to_grader: Option<bool>,
     pub grader_names_visible_to_final_grader: Option<bool>,
@@ -81,45 +84,163 @@ pub struct Assignment {
 }
 
 impl Assignment {
-    api_get! {
-        /// List students eligible to submit this assignment.
-        get_gradeable_students(self):
-            "courses/{course_id}/assignments/{id}/gradeable_students" =>
-            (
-                course_id: self.course_id.ok_or_else(|| anyhow!("Field `course_id` missing"))?,
-                id: self.id.ok_or_else(|| anyhow!("Field `id` missing"))?,
-            ) -> () -> [UserDisplay]
+    /// Check whether the due date of the assignment has passed.
+    ///
+    /// The `chrono` feature is needed to use this function.
+    #[cfg(feature = "chrono")]
+    pub fn is_overdue(&self) -> bool {
+        self.due_at
+            .is_some_and(|due_at| due_at < chrono::Utc::now())
     }
 
-    api_get! {
-        /// Get a single submission, based on user id.
-        get_submission(self):
-             "courses/{course_id}/assignments/{id}/submissions/{user_id}" =>
-            (
-                course_id: self.course_id.ok_or_else(|| anyhow!("Field `course_id` missing"))?,
-                id: self.id.ok_or_else(|| anyhow!("Field `id` missing"))?,
-            ) -> (user_id: usize) -> Submission
+    /// Check whether the assignment is locked at the given time: before it is unlocked, or after it
+    /// is locked.
+    ///
+    /// The `chrono` feature is needed to use this function.
+    #[cfg(feature = "chrono")]
+    pub fn is_locked_at(&self, now: Timestamp) -> bool {
+        crate::datetime::is_locked_at(self.unlock_at, self.lock_at, now)
     }
 
-    api_get! {
-        /// Get all existing submissions for this assignment.
-        get_submissions(self):
-            "courses/{course_id}/assignments/{id}/submissions" =>
-            (
-                course_id: self.course_id.ok_or_else(|| anyhow!("Field `course_id` missing"))?,
-                id: self.id.ok_or_else(|| anyhow!("Field `id` missing"))?,
-            ) -> () -> [Submission]
+    /// Return the time until the assignment is due, which is negative when it is overdue, or `None`
+    /// when it has no due date.
+    ///
+    /// The `chrono` feature is needed to use this function.
+    #[cfg(feature = "chrono")]
+    pub fn time_until_due(&self) -> Option<chrono::Duration> {
+        self.due_at.map(|due_at| due_at - chrono::Utc::now())
     }
+
+    /// List students eligible to submit this assignment.
+    pub fn get_gradeable_students(&self) -> Result<PagedQuery<GetGradeableStudents>, CanvasError> {
+        Ok(GetGradeableStudents {
+            course_id: self
+                .course_id
+                .clone()
+                .ok_or_else(|| CanvasError::missing_field("course_id"))?,
+            assignment_id: self.id.ok_or_else(|| CanvasError::missing_field("id"))?,
+        }
+        .query())
+    }
+
+    /// Get a single submission, based on user id.
+    pub fn get_submission(
+        &self,
+        user_id: impl Into<UserId>,
+    ) -> Result<Query<GetSubmission>, CanvasError> {
+        Ok(GetSubmission {
+            course_id: self
+                .course_id
+                .clone()
+                .ok_or_else(|| CanvasError::missing_field("course_id"))?,
+            assignment_id: self.id.ok_or_else(|| CanvasError::missing_field("id"))?,
+            user_id: user_id.into(),
+            ..Default::default()
+        }
+        .query())
+    }
+
+    /// Get all existing submissions for this assignment.
+    pub fn get_submissions(&self) -> Result<PagedQuery<GetSubmissions>, CanvasError> {
+        Ok(GetSubmissions {
+            course_id: self
+                .course_id
+                .clone()
+                .ok_or_else(|| CanvasError::missing_field("course_id"))?,
+            assignment_id: self.id.ok_or_else(|| CanvasError::missing_field("id"))?,
+            ..Default::default()
+        }
+        .query())
+    }
+}
+
+/// List students eligible to submit an assignment.
+#[canvas_endpoint(
+    GET,
+    "courses/{course_id}/assignments/{assignment_id}/gradeable_students",
+    paged,
+    output = UserDisplay
+)]
+pub struct GetGradeableStudents {
+    pub course_id: CourseId,
+    pub assignment_id: AssignmentId,
+}
+
+/// Get a single submission of an assignment, based on user id.
+#[canvas_endpoint(
+    GET,
+    "courses/{course_id}/assignments/{assignment_id}/submissions/{user_id}",
+    output = Submission
+)]
+#[derive(Default)]
+pub struct GetSubmission {
+    pub course_id: CourseId,
+    pub assignment_id: AssignmentId,
+    pub user_id: UserId,
+    /// Extra information to include with the submission.
+    pub include: Vec<SubmissionInclude>,
+}
+
+/// Get all existing submissions of an assignment.
+#[canvas_endpoint(
+    GET,
+    "courses/{course_id}/assignments/{assignment_id}/submissions",
+    paged,
+    output = Submission
+)]
+#[derive(Default)]
+pub struct GetSubmissions {
+    pub course_id: CourseId,
+    pub assignment_id: AssignmentId,
+    /// Extra information to include with the submissions.
+    pub include: Vec<SubmissionInclude>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct AssignmentGroup {
-    pub id: usize,
+    pub id: AssignmentGroupId,
     pub name: Option<String>,
     pub position: Option<usize>,
     pub group_weight: Option<usize>,
     pub sis_source_id: Option<String>,
     // pub integration_data: usize,
-    pub assignments: Option<Vec<usize>>,
+    pub assignments: Option<Vec<Assignment>>,
     // pub rules: usize,
 }
+
+api_parameter! {
+    /// Extra information to include with assignments.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    AssignmentInclude => "include[]",
+    Submission => "submission",
+    AssignmentVisibility => "assignment_visibility",
+    AllDates => "all_dates",
+    Overrides => "overrides",
+    ObservedUsers => "observed_users",
+    CanEdit => "can_edit",
+    ScoreStatistics => "score_statistics",
+}
+
+api_parameter! {
+    /// The order of a list of assignments.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    AssignmentOrder => "order_by",
+    Position => "position",
+    Name => "name",
+    DueAt => "due_at",
+}
+
+api_parameter! {
+    /// Extra information to include with assignment groups.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    AssignmentGroupInclude => "include[]",
+    Assignments => "assignments",
+    DiscussionTopic => "discussion_topic",
+    AllDates => "all_dates",
+    AssignmentVisibility => "assignment_visibility",
+    Overrides => "overrides",
+    Submission => "submission",
+    ObservedUsers => "observed_users",
+    CanEdit => "can_edit",
+    ScoreStatistics => "score_statistics",
+}
@@ -3,6 +3,9 @@
 use serde::{Deserialize, Serialize};
 
 use crate::canvas::CanvasInformation;
+use crate::endpoint::{canvas_endpoint, GetObjectRequestMap, PagedQuery, Query};
+use crate::error::CanvasError;
+use crate::ids::*;
 use crate::models::prelude::*;
 use crate::parameters::*;
 
@@ -19,11 +22,10 @@ impl Canvas {
         conversations_get_running_batches()
     }
 
-    api_get! {
-        /// Get the number of unread conversations for the current user.
-        conversations_unread_count():
-            "conversations/unread_count" =>
-                () -> () -> { "unread_count": usize }
+    /// Get the number of unread conversations for the current user.
+    pub fn conversations_unread_count(
+    ) -> Result<GetObjectRequestMap<usize, ConversationsUnreadCount>, CanvasError> {
+        Ok(ConversationsUnreadCount.query())
     }
 
     api_todo! {
@@ -89,11 +91,15 @@ impl Canvas {
         get_conversations()
     }
 
-    api_get! {
-        /// Get a course with a specific id.
-        get_course():
-            "courses/{id}" =>
-                () -> (id: usize) -> Course
+    /// Get a course with a specific id.
+    ///
+    /// The id can also be the SIS id of the course, with [`CourseId::sis`].
+    pub fn get_course(id: impl Into<CourseId>) -> Result<Query<GetCourse>, CanvasError> {
+        Ok(GetCourse {
+            course_id: id.into(),
+            ..Default::default()
+        }
+        .query())
     }
 
     api_todo! {
@@ -194,11 +200,9 @@ impl Canvas {
         get_section()
     }
 
-    api_get! {
-        /// Return the current user's list of todo items, as seen on the user dashboard.
-        get_todo_items():
-            "users/self/todo" =>
-                () -> () -> [Todo]
+    /// Return the current user's list of todo items, as seen on the user dashboard.
+    pub fn get_todo_items() -> Result<PagedQuery<GetTodoItems>, CanvasError> {
+        Ok(GetTodoItems.query())
     }
 
     api_todo! {
@@ -228,28 +232,13 @@ impl Canvas {
         search: String,
         public_only: bool,
         open_enrollment_only: bool,
-    ) -> anyhow::Result<GetPagedObjectRequest<serde_json::Value>> {
-        let mut request = GetPagedObjectRequest::<_>::new("search/all_courses/".to_string())
-            .add_parameter(RequestParameter {
-                name: "search".to_string(),
-                value: search,
-            });
-
-        if public_only {
-            request = request.add_parameter(RequestParameter {
-                name: "public_only".into(),
-                value: public_only.to_string(),
-            });
-        }
-
-        if open_enrollment_only {
-            request = request.add_parameter(RequestParameter {
-                name: "open_enrollment_only".into(),
-                value: open_enrollment_only.to_string(),
-            });
+    ) -> Result<PagedQuery<SearchCourses>, CanvasError> {
+        Ok(SearchCourses {
+            search,
+            public_only: public_only.then_some(true),
+            open_enrollment_only: open_enrollment_only.then_some(true),
         }
-
-        Ok(request)
+        .query())
     }
 
     api_todo! {
@@ -259,3 +248,28 @@ impl Canvas {
         search_recipients()
     }
 }
+
+/// Get the number of unread conversations for the current user.
+#[canvas_endpoint(GET, "conversations/unread_count", output = usize, key = "unread_count")]
+pub struct ConversationsUnreadCount;
+
+/// Get a course with a specific id.
+#[canvas_endpoint(GET, "courses/{course_id}", output = Course)]
+#[derive(Default)]
+pub struct GetCourse {
+    pub course_id: CourseId,
+    /// Extra information to include with the course.
+    pub include: Vec<CourseInclude>,
+}
+
+/// Return the current user's list of todo items, as seen on the user dashboard.
+#[canvas_endpoint(GET, "users/self/todo", paged, output = Todo)]
+pub struct GetTodoItems;
+
+/// Returns a list of courses that match the search criteria.
+#[canvas_endpoint(GET, "search/all_courses/", paged, output = serde_json::Value)]
+pub struct SearchCourses {
+    pub search: String,
+    pub public_only: Option<bool>,
+    pub open_enrollment_only: Option<bool>,
+}
@@ -1,8 +1,13 @@
 //! Model for managing course information.
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::canvas::CanvasInformation;
+use crate::datetime::Timestamp;
+use crate::endpoint::{canvas_endpoint, PagedQuery, Query};
+use crate::error::CanvasError;
+use crate::ids::*;
 use crate::models::prelude::*;
 use crate::parameters::*;
 
@@ -10,7 +15,7 @@ use crate::requests::*;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Course {
-    pub id: usize,
+    pub id: CourseId,
     pub sis_course_id: Option<String>,
     pub uuid: Option<String>,
     pub integration_id: Option<usize>,
@@ -18,14 +23,14 @@ pub struct Course {
     pub name: Option<String>,
     pub course_code: Option<String>,
     pub workflow_state: Option<String>,
-    pub account_id: Option<usize>,
-    pub root_account_id: Option<usize>,
-    pub enrollment_term_id: Option<usize>,
-    pub grading_standard_id: Option<usize>,
+    pub account_id: Option<AccountId>,
+    pub root_account_id: Option<AccountId>,
+    pub enrollment_term_id: Option<EnrollmentTermId>,
+    pub grading_standard_id: Option<GradingStandardId>,
     pub grade_passback_setting: Option<String>,
-    pub created_at: Option<String>,
-    pub start_at: Option<String>,
-    pub end_at: Option<String>,
+    pub created_at: Option<Timestamp>,
+    pub start_at: Option<Timestamp>,
+    pub end_at: Option<Timestamp>,
     pub locale: Option<String>,
     // pub enrollments: Vec<>,
     pub total_students: Option<usize>,
@@ -61,73 +66,105 @@ pub struct Course {
 }
 
 impl Course {
-    api_todo! {
+    api_post! {
         /// Create a new grading standard for the course.
-        add_grading_standards(self)
+        ///
+        /// The `title` and `grading_scheme_entry` of the grading standard are passed as the body
+        /// of the request.
+        add_grading_standards(self):
+            "courses/{id}/grading_standards" =>
+                (id: self.id.clone()) -> () -> Value
     }
 
-    api_todo! {
+    api_delete! {
         /// Mark this course as concluded.
-        conclude(self)
+        conclude(self):
+            "courses/{id}" =>
+                (id: self.id.clone()) -> () -> Value
+                [CourseEvent::Conclude]
     }
 
-    api_todo! {
+    api_post! {
         /// Create a new assignment for this course.
         ///
         /// Note: the assignment is created in the active state.
-        create_assignment(self)
+        ///
+        /// The assignment is passed as the body of the request:
+        ///
+        /// ```ignore
+        /// course
+        ///     .create_assignment()?
+        ///     .body(serde_json::json!({ "assignment": { "name": "Lab 1" } }))?
+        ///     .fetch(&canvas)
+        ///     .await?;
+        /// ```
+        create_assignment(self):
+            "courses/{id}/assignments" =>
+                (id: self.id.clone()) -> () -> Assignment
     }
 
-    api_todo! {
+    api_post! {
         /// Create a new assignment group for this course.
-        create_assignment_group(self)
+        create_assignment_group(self):
+            "courses/{id}/assignment_groups" =>
+                (id: self.id.clone()) -> () -> AssignmentGroup
     }
 
-    api_todo! {
+    api_post! {
         /// Create the specified overrides for each assignment.
-        create_assignment_overrides(self)
+        create_assignment_overrides(self):
+            "courses/{id}/assignments/overrides" =>
+                (id: self.id.clone()) -> () -> Value
     }
 
-    api_get! {
-        /// Get all courses from the current user.
-        courses():
-            "courses" =>
-            () -> () -> [Course]
+    /// Get all courses from the current user.
+    pub fn courses() -> Result<PagedQuery<GetCourses>, CanvasError> {
+        Ok(GetCourses::default().query())
     }
 
-    // api_get! {
-    // /// Get a course with a specific Canvas ID.
-    // with_id():
-    // "courses/{id}" =>
-    // () -> (id: usize) -> Course
-    // }
-
-    api_get! {
-        /// Get all outcome links for context - BETA
-        get_all_outcome_links_in_context(self):
-            "courses/{id}/outcome_group_links" =>
-                (id: self.id) -> () -> [String]
+    /// Get all outcome links for context - BETA
+    pub fn get_all_outcome_links_in_context(
+        &self,
+    ) -> Result<PagedQuery<GetAllOutcomeLinksInContext>, CanvasError> {
+        Ok(GetAllOutcomeLinksInContext {
+            course_id: self.id.clone(),
+        }
+        .query())
     }
 
-    api_get! {
-        /// Return the assignment with the given id.
-        get_assignment(self):
-            "courses/{id}/assignments/{assignment_id}" =>
-                (id: self.id) -> (assignment_id: usize) -> Assignment
+    /// Return the assignment with the given id.
+    pub fn get_assignment(
+        &self,
+        assignment_id: impl Into<AssignmentId>,
+    ) -> Result<Query<GetAssignment>, CanvasError> {
+        Ok(GetAssignment {
+            course_id: self.id.clone(),
+            assignment_id: assignment_id.into(),
+            ..Default::default()
+        }
+        .query())
     }
 
-    api_get! {
-        /// Retrieve specified assignment group for the specified course.
-        get_assignment_group(self):
-            "courses/{id}/assignment_groups/{assignment_group_id}" =>
-                (id: self.id) -> (assignment_group_id: usize) -> AssignmentGroup
+    /// Retrieve specified assignment group for the specified course.
+    pub fn get_assignment_group(
+        &self,
+        assignment_group_id: impl Into<AssignmentGroupId>,
+    ) -> Result<Query<GetAssignmentGroup>, CanvasError> {
+        Ok(GetAssignmentGroup {
+            course_id: self.id.clone(),
+            assignment_group_id: assignment_group_id.into(),
+            ..Default::default()
+        }
+        .query())
     }
 
-    api_get! {
-        /// List assignment groups for the specified course.
-        get_assignment_groups(self):
-            "course/{id}/assignment_groups" =>
-                (id: self.id) -> () -> [AssignmentGroup]
+    /// List assignment groups for the specified course.
+    pub fn get_assignment_groups(&self) -> Result<PagedQuery<GetAssignmentGroups>, CanvasError> {
+        Ok(GetAssignmentGroups {
+            course_id: self.id.clone(),
+            ..Default::default()
+        }
+        .query())
     }
 
     api_todo! {
@@ -136,40 +173,245 @@ impl Course {
         get_assignment_overrides(self)
     }
 
-    api_get! {
-        /// Get all the assignments of a course.
-        get_assignments(self):
-            "courses/{id}/assignments" =>
-            (id: self.id) -> () -> [Assignment]
+    /// Get all the assignments of a course.
+    pub fn get_assignments(&self) -> Result<PagedQuery<GetAssignments>, CanvasError> {
+        Ok(GetAssignments {
+            course_id: self.id.clone(),
+            ..Default::default()
+        }
+        .query())
     }
 
-    api_get! {
-        /// Returns a list of assignments for the given assignment group.
-        get_assignments_for_group(self):
-            "courses/{id}/assignment_groups/{assignment_group_id}/assignments" =>
-                (id: self.id) -> (assignment_group_id: usize) -> [Assignment]
+    /// Returns a list of assignments for the given assignment group.
+    pub fn get_assignments_for_group(
+        &self,
+        assignment_group_id: impl Into<AssignmentGroupId>,
+    ) -> Result<PagedQuery<GetAssignmentsForGroup>, CanvasError> {
+        Ok(GetAssignmentsForGroup {
+            course_id: self.id.clone(),
+            assignment_group_id: assignment_group_id.into(),
+            ..Default::default()
+        }
+        .query())
     }
 
-    api_get! {
-        /// Get all the users from the course.
-        /// This includes: teachers, students, teacher assistants...
-        get_users(self):
-            "courses/{id}/search_users" =>
-            (id: self.id) -> () -> [User]
+    /// Get all the users from the course.
+    /// This includes: teachers, students, teacher assistants...
+    pub fn get_users(&self) -> Result<PagedQuery<GetUsers>, CanvasError> {
+        Ok(GetUsers {
+            course_id: self.id.clone(),
+            ..Default::default()
+        }
+        .query())
     }
 
-    api_get! {
-        /// Get only the students from the course.
-        get_students(self):
-            "courses/{id}/search_users" =>
-            (id: self.id) -> () -> [User]
-            [EnrollmentType::Student]
+    /// Get only the students from the course.
+    pub fn get_students(&self) -> Result<PagedQuery<GetStudents>, CanvasError> {
+        Ok(GetStudents {
+            course_id: self.id.clone(),
+            ..Default::default()
+        }
+        .query())
     }
 
-    api_get! {
-        /// Get all the files of a course.
-        get_files(self):
-            "courses/{id}/files" =>
-            (id: self.id) -> () -> [File]
+    /// Get all the files of a course.
+    pub fn get_files(&self) -> Result<PagedQuery<GetFiles>, CanvasError> {
+        Ok(GetFiles {
+            course_id: self.id.clone(),
+            ..Default::default()
+        }
+        .query())
     }
 }
+
+/// Get all courses from the current user.
+#[canvas_endpoint(GET, "courses", paged, output = Course)]
+#[derive(Default)]
+pub struct GetCourses {
+    /// Only return courses where the user is enrolled as this type.
+    pub enrollment_type: Option<EnrollmentType>,
+    /// Only return courses where the user has an enrollment with this state.
+    pub enrollment_state: Option<EnrollmentState>,
+    /// Extra information to include with the courses.
+    pub include: Vec<CourseInclude>,
+}
+
+/// Get all outcome links for context - BETA
+#[canvas_endpoint(GET, "courses/{course_id}/outcome_group_links", paged, output = String)]
+pub struct GetAllOutcomeLinksInContext {
+    pub course_id: CourseId,
+}
+
+/// Return the assignment of a course with the given id.
+#[canvas_endpoint(
+    GET,
+    "courses/{course_id}/assignments/{assignment_id}",
+    output = Assignment
+)]
+#[derive(Default)]
+pub struct GetAssignment {
+    pub course_id: CourseId,
+    pub assignment_id: AssignmentId,
+    /// Extra information to include with the assignment.
+    pub include: Vec<AssignmentInclude>,
+}
+
+/// Retrieve specified assignment group for the specified course.
+#[canvas_endpoint(
+    GET,
+    "courses/{course_id}/assignment_groups/{assignment_group_id}",
+    output = AssignmentGroup
+)]
+#[derive(Default)]
+pub struct GetAssignmentGroup {
+    pub course_id: CourseId,
+    pub assignment_group_id: AssignmentGroupId,
+    /// Extra information to include with the assignment group.
+    pub include: Vec<AssignmentGroupInclude>,
+}
+
+/// List assignment groups for the specified course.
+#[canvas_endpoint(
+    GET,
+    "course/{course_id}/assignment_groups",
+    paged,
+    output = AssignmentGroup
+)]
+#[derive(Default)]
+pub struct GetAssignmentGroups {
+    pub course_id: CourseId,
+    /// Extra information to include with the assignment groups.
+    pub include: Vec<AssignmentGroupInclude>,
+}
+
+/// Get all the assignments of a course.
+#[canvas_endpoint(GET, "courses/{course_id}/assignments", paged, output = Assignment)]
+#[derive(Default)]
+pub struct GetAssignments {
+    pub course_id: CourseId,
+    /// Only return the assignments of which the name contains this term.
+    pub search_term: Option<String>,
+    /// The order of the assignments.
+    pub order_by: Option<AssignmentOrder>,
+    /// Extra information to include with the assignments.
+    pub include: Vec<AssignmentInclude>,
+}
+
+/// Returns a list of assignments for the given assignment group.
+#[canvas_endpoint(
+    GET,
+    "courses/{course_id}/assignment_groups/{assignment_group_id}/assignments",
+    paged,
+    output = Assignment
+)]
+#[derive(Default)]
+pub struct GetAssignmentsForGroup {
+    pub course_id: CourseId,
+    pub assignment_group_id: AssignmentGroupId,
+    /// Only return the assignments of which the name contains this term.
+    pub search_term: Option<String>,
+    /// The order of the assignments.
+    pub order_by: Option<AssignmentOrder>,
+    /// Extra information to include with the assignments.
+    pub include: Vec<AssignmentInclude>,
+}
+
+/// Get all the users from a course.
+/// This includes: teachers, students, teacher assistants...
+#[canvas_endpoint(GET, "courses/{course_id}/search_users", paged, output = User)]
+#[derive(Default)]
+pub struct GetUsers {
+    pub course_id: CourseId,
+    /// Only return the users of which the name, login or email contains this term.
+    pub search_term: Option<String>,
+    /// Return the page with this user instead of the requested page, when the user is one of the
+    /// users.
+    pub user_id: Option<UserId>,
+    /// The field on which the users are sorted.
+    pub sort: Option<SortOn>,
+    /// Only return the users that are enrolled as this type.
+    pub enrollment_type: Vec<EnrollmentType>,
+    /// Only return the users that have an enrollment with this state.
+    pub enrollment_state: Vec<EnrollmentState>,
+    /// Extra information to include with the users.
+    pub include: Vec<UserInclude>,
+}
+
+/// Get only the students from a course.
+#[canvas_endpoint(
+    GET,
+    "courses/{course_id}/search_users",
+    paged,
+    output = User,
+    parameter = EnrollmentType::Student
+)]
+#[derive(Default)]
+pub struct GetStudents {
+    pub course_id: CourseId,
+    /// Only return the students of which the name, login or email contains this term.
+    pub search_term: Option<String>,
+    /// Return the page with this user instead of the requested page, when the user is one of the
+    /// students.
+    pub user_id: Option<UserId>,
+    /// The field on which the students are sorted.
+    pub sort: Option<SortOn>,
+    /// Only return the students that have an enrollment with this state.
+    pub enrollment_state: Vec<EnrollmentState>,
+    /// Extra information to include with the students.
+    pub include: Vec<UserInclude>,
+}
+
+/// Get all the files of a course.
+#[canvas_endpoint(GET, "courses/{course_id}/files", paged, output = File)]
+#[derive(Default)]
+pub struct GetFiles {
+    pub course_id: CourseId,
+    /// Only return the files of which the name contains this term.
+    pub search_term: Option<String>,
+    /// Only return the files with this content type, for example `image`.
+    pub content_types: Vec<String>,
+    /// The field on which the files are sorted.
+    pub sort: Option<FileSort>,
+    /// Extra information to include with the files.
+    pub include: Vec<FileInclude>,
+}
+
+api_parameter! {
+    /// Extra information to include with courses.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    CourseInclude => "include[]",
+    NeedsGradingCount => "needs_grading_count",
+    SyllabusBody => "syllabus_body",
+    PublicDescription => "public_description",
+    TotalScores => "total_scores",
+    CurrentGradingPeriodScores => "current_grading_period_scores",
+    /// The information for the enrollment term for each course is returned.
+    Term => "term",
+    Account => "account",
+    CourseProgress => "course_progress",
+    Sections => "sections",
+    StorageQuotaUsedMb => "storage_quota_used_mb",
+    TotalStudents => "total_students",
+    PassbackStatus => "passback_status",
+    Favorites => "favorites",
+    Teachers => "teachers",
+    ObservedUsers => "observed_users",
+    Tabs => "tabs",
+    CourseImage => "course_image",
+    BannerImage => "banner_image",
+    Concluded => "concluded",
+}
+
+api_parameter! {
+    /// Extra information to include with the users of a course.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    UserInclude => "include[]",
+    Enrollments => "enrollments",
+    Locked => "locked",
+    AvatarUrl => "avatar_url",
+    TestStudent => "test_student",
+    Bio => "bio",
+    CustomLinks => "custom_links",
+    CurrentGradingPeriodScores => "current_grading_period_scores",
+    Uuid => "uuid",
+}
@@ -1,31 +1,33 @@
 //! Model for managing files with Canvas.
 
-use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
 
 use crate::canvas::CanvasInformation;
+use crate::datetime::Timestamp;
+use crate::error::CanvasError;
+use crate::ids::*;
 use crate::models::prelude::*;
 use crate::parameters::*;
 use crate::requests::*;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct File {
-    pub id: usize,
+    pub id: FileId,
     pub uuid: Option<String>,
-    pub folder_id: Option<usize>,
+    pub folder_id: Option<FolderId>,
     pub display_name: Option<String>,
     pub filename: Option<String>,
     #[serde(rename(deserialize = "content-type"))]
     pub content_type: Option<String>,
     pub url: Option<String>,
     pub size: Option<usize>,
-    pub created_at: Option<String>,
-    pub updated_at: Option<String>,
-    pub unlock_at: Option<String>,
+    pub created_at: Option<Timestamp>,
+    pub updated_at: Option<Timestamp>,
+    pub unlock_at: Option<Timestamp>,
     pub locked: Option<bool>,
     pub hidden: Option<bool>,
     pub thumbnail_url: Option<String>,
-    pub modified_at: Option<String>,
+    pub modified_at: Option<Timestamp>,
     pub mime_class: Option<String>,
     pub media_entry_id: Option<String>,
     pub locked_for_user: Option<bool>,
@@ -35,45 +37,55 @@ pub struct File {
 }
 
 impl File {
-    #[cfg(not(feature = "blocking"))]
-    pub async fn download(&self, canvas: &CanvasInformation<'_>, path: &str) -> anyhow::Result<()> {
+    /// Download the file into the directory `path`.
+    pub async fn download(
+        &self,
+        canvas: &CanvasInformation,
+        path: &str,
+    ) -> Result<(), CanvasError> {
         let url = self
             .url
             .clone()
-            .ok_or_else(|| anyhow!("File url not set"))?;
+            .ok_or_else(|| CanvasError::missing_field("url"))?;
         let name = self
             .filename
             .clone()
-            .ok_or_else(|| anyhow!("File name not set"))?;
+            .ok_or_else(|| CanvasError::missing_field("filename"))?;
 
-        let mut resp = canvas.get_request(url).send().await?.bytes().await?;
+        instrument!(("download", file_id = self.id.get()), async move {
+            let resp = canvas
+                .send_bypassing_cache(canvas.get_request(url)?)
+                .await?
+                .error_for_status()?
+                .body;
 
-        std::fs::write(format!("{path}/{name}"), resp)?;
+            event!(DEBUG, bytes = resp.len(), "downloaded");
+            std::fs::write(format!("{path}/{name}"), resp)?;
 
-        Ok(())
+            Ok(())
+        })
+        .await
     }
 
+    /// Download the file into the directory `path`, blocking the current thread until the file
+    /// is received.
+    ///
+    /// # Panics
+    /// Panics when called from within an asynchronous runtime. Use `download` there.
     #[cfg(feature = "blocking")]
-    pub fn download(&self, canvas: &CanvasInformation<'_>, path: &str) -> anyhow::Result<()> {
-        let url = self
-            .url
-            .clone()
-            .ok_or_else(|| anyhow!("File url not set"))?;
-        let name = self
-            .filename
-            .clone()
-            .ok_or_else(|| anyhow!("File name not set"))?;
-
-        let mut resp = canvas.get_request(url).send()?.bytes()?;
-
-        std::fs::write(format!("{path}/{name}"), resp)?;
-
-        Ok(())
+    pub fn download_blocking(
+        &self,
+        canvas: &CanvasInformation,
+        path: &str,
+    ) -> Result<(), CanvasError> {
+        canvas.block_on(self.download(canvas, path))
     }
 
-    api_todo! {
+    api_delete! {
         /// Delete this file
-        delete(self)
+        delete(self):
+            "files/{id}" =>
+                (id: self.id) -> () -> File
     }
 
     api_todo! {
@@ -81,3 +93,23 @@ impl File {
         get_contents(self)
     }
 }
+
+api_parameter! {
+    /// Extra information to include with files.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    FileInclude => "include[]",
+    User => "user",
+    UsageRights => "usage_rights",
+}
+
+api_parameter! {
+    /// The field on which a list of files is sorted.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    FileSort => "sort",
+    Name => "name",
+    Size => "size",
+    CreatedAt => "created_at",
+    UpdatedAt => "updated_at",
+    ContentType => "content_type",
+    User => "user",
+}
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::canvas::CanvasInformation;
+use crate::ids::*;
 use crate::models::prelude::*;
 use crate::parameters::*;
 
@@ -10,9 +11,9 @@ use crate::requests::*;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Outcome {
-    pub id: usize,
+    pub id: OutcomeId,
     pub url: Option<String>,
-    pub context_id: Option<usize>,
+    pub context_id: Option<ContextId>,
     pub context_type: Option<String>,
     pub title: Option<String>,
     pub display_name: Option<String>,
@@ -28,3 +29,19 @@ pub struct Outcome {
     pub accessed: Option<bool>,
     pub has_updateable_rubrics: Option<bool>,
 }
+
+impl Outcome {
+    /// The course of the outcome, when its context is a course.
+    pub fn course_id(&self) -> Option<CourseId> {
+        self.context_of("Course").map(CourseId::Id)
+    }
+
+    /// The account of the outcome, when its context is an account.
+    pub fn account_id(&self) -> Option<AccountId> {
+        self.context_of("Account").map(AccountId::Id)
+    }
+
+    fn context_of(&self, context_type: &str) -> Option<u64> {
+        (self.context_type.as_deref() == Some(context_type)).then_some(self.context_id?.get())
+    }
+}
@@ -1,40 +1,59 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::datetime::Timestamp;
+use crate::ids::*;
+
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AllDates {
-    pub due_at: Option<String>,
-    pub id: Option<i64>,
-    pub lock_at: Option<String>,
-    pub set_id: Option<i64>,
+    pub due_at: Option<Timestamp>,
+    pub id: Option<AssignmentOverrideId>,
+    pub lock_at: Option<Timestamp>,
+    pub set_id: Option<OverrideSetId>,
     pub set_type: Option<String>,
     pub title: Option<String>,
-    pub unlock_at: Option<String>,
+    pub unlock_at: Option<Timestamp>,
+}
+
+impl AllDates {
+    /// The section to which the dates apply, when they are overridden for a section.
+    pub fn section_id(&self) -> Option<SectionId> {
+        self.set_of("CourseSection").map(SectionId)
+    }
+
+    /// The group to which the dates apply, when they are overridden for a group.
+    pub fn group_id(&self) -> Option<GroupId> {
+        self.set_of("Group").map(GroupId)
+    }
+
+    fn set_of(&self, set_type: &str) -> Option<u64> {
+        (self.set_type.as_deref() == Some(set_type)).then_some(self.set_id?.get())
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LockInfo {
-    pub unlock_at: Option<String>,
+    pub unlock_at: Option<Timestamp>,
     pub asset_string: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Quiz {
-    pub id: Option<i64>,
+    pub id: Option<QuizId>,
     pub title: Option<String>,
     pub html_url: Option<String>,
     pub mobile_url: Option<String>,
     pub preview_url: Option<String>,
     pub description: Option<String>,
     pub quiz_type: Option<String>,
-    pub assignment_group_id: Option<i64>,
+    pub assignment_group_id: Option<AssignmentGroupId>,
     pub time_limit: Option<i64>,
     pub shuffle_answers: Option<bool>,
     pub hide_results: Option<String>,
     pub show_correct_answers: Option<bool>,
     pub show_correct_answers_last_attempt: Option<bool>,
-    pub show_correct_answers_at: Option<String>,
-    pub hide_correct_answers_at: Option<String>,
+    pub show_correct_answers_at: Option<Timestamp>,
+    pub hide_correct_answers_at: Option<Timestamp>,
     pub one_time_results: Option<bool>,
     pub scoring_policy: Option<String>,
     pub allowed_attempts: Option<i64>,
@@ -44,9 +63,9 @@ pub struct Quiz {
     pub cant_go_back: Option<bool>,
     pub access_code: Option<String>,
     pub ip_filter: Option<String>,
-    pub due_at: Option<String>,
-    pub lock_at: Option<String>,
-    pub unlock_at: Option<String>,
+    pub due_at: Option<Timestamp>,
+    pub lock_at: Option<Timestamp>,
+    pub unlock_at: Option<Timestamp>,
     pub published: Option<bool>,
     pub unpublishable: Option<bool>,
     pub locked_for_user: Option<bool>,
@@ -60,3 +79,32 @@ pub struct Quiz {
     pub question_types: Option<Vec<String>>,
     pub anonymous_submissions: Option<bool>,
 }
+
+impl Quiz {
+    /// Check whether the due date of the quiz has passed.
+    ///
+    /// The `chrono` feature is needed to use this function.
+    #[cfg(feature = "chrono")]
+    pub fn is_overdue(&self) -> bool {
+        self.due_at
+            .is_some_and(|due_at| due_at < chrono::Utc::now())
+    }
+
+    /// Check whether the quiz is locked at the given time: before it is unlocked, or after it
+    /// is locked.
+    ///
+    /// The `chrono` feature is needed to use this function.
+    #[cfg(feature = "chrono")]
+    pub fn is_locked_at(&self, now: Timestamp) -> bool {
+        crate::datetime::is_locked_at(self.unlock_at, self.lock_at, now)
+    }
+
+    /// Return the time until the quiz is due, which is negative when it is overdue, or `None`
+    /// when it has no due date.
+    ///
+    /// The `chrono` feature is needed to use this function.
+    #[cfg(feature = "chrono")]
+    pub fn time_until_due(&self) -> Option<chrono::Duration> {
+        self.due_at.map(|due_at| due_at - chrono::Utc::now())
+    }
+}
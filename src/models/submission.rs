@@ -3,15 +3,18 @@
 use serde::{Deserialize, Serialize};
 
 use crate::canvas::CanvasInformation;
+use crate::datetime::Timestamp;
+use crate::ids::*;
 use crate::models::prelude::*;
 use crate::parameters::*;
 use crate::requests::*;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Submission {
-    pub assignment_id: Option<usize>,
-    pub assignment: Option<usize>,
-    pub course: Option<usize>,
+    pub id: Option<SubmissionId>,
+    pub assignment_id: Option<AssignmentId>,
+    pub assignment: Option<Assignment>,
+    pub course: Option<Course>,
     pub attempt: Option<usize>,
     pub body: Option<String>,
     pub grade: Option<String>,
@@ -21,12 +24,12 @@ pub struct Submission {
     pub score: Option<f64>,
     pub submission_comments: Option<Vec<String>>,
     pub submission_type: Option<String>,
-    pub submitted_at: Option<String>,
+    pub submitted_at: Option<Timestamp>,
     pub url: Option<String>,
-    pub user_id: Option<usize>,
-    pub grader_id: Option<usize>,
-    pub graded_at: Option<String>,
-    pub user: Option<usize>,
+    pub user_id: Option<UserId>,
+    pub grader_id: Option<UserId>,
+    pub graded_at: Option<Timestamp>,
+    pub user: Option<User>,
     pub late: bool,
     pub assignment_visible: Option<bool>,
     pub excused: Option<bool>,
@@ -37,5 +40,20 @@ pub struct Submission {
     pub workflow_state: Option<String>,
     pub extra_attempts: Option<usize>,
     pub anonymous_id: Option<String>,
-    pub posted_at: Option<String>,
+    pub posted_at: Option<Timestamp>,
+}
+
+api_parameter! {
+    /// Extra information to include with submissions.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    SubmissionInclude => "include[]",
+    SubmissionHistory => "submission_history",
+    SubmissionComments => "submission_comments",
+    RubricAssessment => "rubric_assessment",
+    Assignment => "assignment",
+    Visibility => "visibility",
+    Course => "course",
+    User => "user",
+    Group => "group",
+    ReadStatus => "read_status",
 }
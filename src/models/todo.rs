@@ -1,3 +1,4 @@
+use crate::ids::*;
 use crate::models::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -12,6 +13,6 @@ pub struct Todo {
     pub html_url: Option<String>,
     pub needs_grading_count: Option<i64>,
     pub context_type: Option<String>,
-    pub course_id: Option<i64>,
-    pub group_id: Option<usize>,
+    pub course_id: Option<CourseId>,
+    pub group_id: Option<GroupId>,
 }
@@ -1,13 +1,15 @@
 //! Model for accessing user information.
 
 use crate::canvas::*;
+use crate::datetime::Timestamp;
+use crate::ids::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct User {
-    pub id: usize,
+    pub id: UserId,
     pub name: Option<String>,
-    pub created_at: Option<String>,
+    pub created_at: Option<Timestamp>,
     pub sortable_name: Option<String>,
     pub short_name: Option<String>,
     pub sis_user_id: Option<String>,
@@ -19,6 +21,6 @@ pub struct User {
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct UserDisplay {
-    pub id: usize,
+    pub id: UserId,
     pub display_name: Option<String>,
 }
@@ -0,0 +1,281 @@
+//! Access Canvas on behalf of a user, with the OAuth2 authorization code flow.
+//!
+//! An [`OAuthClient`] holds the developer key of the application. The user is sent to the
+//! [authorize url](OAuthClient::authorize_url), and Canvas redirects back to the redirect uri with
+//! a code, which is [exchanged](OAuthClient::exchange_code) for an [`OAuthToken`].
+//!
+//! The Canvas information that is built with the token
+//! [refreshes](OAuthClient::refresh) it automatically when Canvas answers with `401 Unauthorized`
+//! because the access token is expired. The current token, for example to store it, is returned by
+//! [`CanvasInformation::oauth_token`](crate::canvas::CanvasInformation::oauth_token).
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use canvasapi::prelude::*;
+//! # tokio_test::block_on(async {
+//! use canvasapi::oauth::OAuthClient;
+//!
+//! let client = OAuthClient::new(
+//!     "https://canvas.test.be",
+//!     "10000000000001",
+//!     "CLIENT_SECRET",
+//!     "https://grading.test.be/oauth/callback",
+//! )
+//! .unwrap()
+//! .scope("url:GET|/api/v1/courses");
+//!
+//! // Redirect the user to this url, and read the code from the callback.
+//! let url = client.authorize_url("RANDOM_STATE");
+//! # let code = "CODE";
+//!
+//! let token = client.exchange_code(code).await.unwrap();
+//! let canvas = CanvasInformation::oauth_builder("https://canvas.test.be", client, token)
+//!     .build()
+//!     .unwrap();
+//! # });
+//! ```
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use reqwest::header::WWW_AUTHENTICATE;
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::canvas::parse_base_url;
+use crate::error::CanvasError;
+use crate::ids::UserId;
+use crate::transport::{HttpRequest, HttpResponse, ReqwestTransport, Transport};
+
+/// The path of the endpoint where the user authorizes the application.
+const AUTHORIZE_PATH: &str = "login/oauth2/auth";
+
+/// The path of the endpoint that issues and deletes tokens.
+pub(crate) const TOKEN_PATH: &str = "login/oauth2/token";
+
+/// The developer key of an application, used to obtain and refresh tokens.
+#[derive(Clone)]
+pub struct OAuthClient {
+    base_url: Url,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+    scopes: Vec<String>,
+    transport: Arc<dyn Transport>,
+}
+
+impl std::fmt::Debug for OAuthClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OAuthClient")
+            .field("base_url", &self.base_url.as_str())
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"<redacted>")
+            .field("redirect_uri", &self.redirect_uri)
+            .field("scopes", &self.scopes)
+            .finish()
+    }
+}
+
+impl OAuthClient {
+    /// Create a client for the developer key with the given id and secret.
+    ///
+    /// The redirect uri must match the one of the developer key. The base URL is validated like
+    /// the one of [`CanvasInformation::builder`](crate::canvas::CanvasInformation::builder).
+    pub fn new(
+        base_url: &str,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        redirect_uri: impl Into<String>,
+    ) -> Result<Self, CanvasError> {
+        let base_url = parse_base_url(base_url)?;
+
+        Ok(Self {
+            base_url,
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            redirect_uri: redirect_uri.into(),
+            scopes: vec![],
+            transport: Arc::new(ReqwestTransport::default()),
+        })
+    }
+
+    /// Request access to a scope, for example `url:GET|/api/v1/courses`.
+    ///
+    /// Without scopes, the token has access to the complete API, unless the developer key
+    /// enforces scopes.
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.scopes.push(scope.into());
+        self
+    }
+
+    /// Send the token requests with another transport than `reqwest`.
+    pub fn transport(mut self, transport: impl Transport) -> Self {
+        self.transport = Arc::new(transport);
+        self
+    }
+
+    /// The url to which the user is sent to authorize the application.
+    ///
+    /// The `state` is passed back to the redirect uri, and should be checked there to prevent
+    /// cross-site request forgery.
+    pub fn authorize_url(&self, state: &str) -> Url {
+        let mut url = self.url(AUTHORIZE_PATH);
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("response_type", "code")
+            .append_pair("redirect_uri", &self.redirect_uri)
+            .append_pair("state", state);
+
+        if !self.scopes.is_empty() {
+            url.query_pairs_mut()
+                .append_pair("scope", &self.scopes.join(" "));
+        }
+
+        url
+    }
+
+    /// Exchange the code that Canvas passed to the redirect uri for a token.
+    pub async fn exchange_code(&self, code: &str) -> Result<OAuthToken, CanvasError> {
+        self.request_token(serde_json::json!({
+            "grant_type": "authorization_code",
+            "client_id": self.client_id,
+            "client_secret": self.client_secret,
+            "redirect_uri": self.redirect_uri,
+            "code": code,
+        }))
+        .await
+    }
+
+    /// Get a new access token with the refresh token of `token`.
+    ///
+    /// The refresh token is kept, as Canvas does not return a new one.
+    pub async fn refresh(&self, token: &OAuthToken) -> Result<OAuthToken, CanvasError> {
+        let refresh_token = token
+            .refresh_token
+            .as_deref()
+            .ok_or_else(|| CanvasError::missing_field("refresh_token"))?;
+
+        let mut refreshed = self
+            .request_token(serde_json::json!({
+                "grant_type": "refresh_token",
+                "client_id": self.client_id,
+                "client_secret": self.client_secret,
+                "refresh_token": refresh_token,
+            }))
+            .await?;
+
+        if refreshed.refresh_token.is_none() {
+            refreshed.refresh_token = Some(refresh_token.to_string());
+        }
+        if refreshed.user.is_none() {
+            refreshed.user = token.user.clone();
+        }
+
+        Ok(refreshed)
+    }
+
+    async fn request_token(&self, body: serde_json::Value) -> Result<OAuthToken, CanvasError> {
+        let request = HttpRequest {
+            method: Method::POST,
+            url: self.url(TOKEN_PATH),
+            headers: Default::default(),
+            body: None,
+        }
+        .json(&body)?;
+
+        let resp = self.transport.send(request).await?.error_for_status()?;
+        let token: TokenResponse = resp.json()?;
+
+        Ok(OAuthToken {
+            access_token: token.access_token,
+            refresh_token: token.refresh_token,
+            expires_at: token
+                .expires_in
+                .map(|seconds| SystemTime::now() + Duration::from_secs(seconds)),
+            user: token.user,
+        })
+    }
+
+    fn url(&self, path: &str) -> Url {
+        Url::parse(&format!(
+            "{}/{path}",
+            self.base_url.as_str().trim_end_matches('/')
+        ))
+        .expect("The base URL is valid")
+    }
+}
+
+/// The tokens that give access to Canvas on behalf of a user.
+///
+/// The token can be serialized, to store it between runs.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OAuthToken {
+    /// The token that is sent with the requests.
+    pub access_token: String,
+    /// The token that is used to get a new access token when it expires.
+    pub refresh_token: Option<String>,
+    /// The time at which the access token expires.
+    pub expires_at: Option<SystemTime>,
+    /// The user on whose behalf the requests are made.
+    pub user: Option<OAuthUser>,
+}
+
+impl std::fmt::Debug for OAuthToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OAuthToken")
+            .field("access_token", &"<redacted>")
+            .field(
+                "refresh_token",
+                &self.refresh_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("expires_at", &self.expires_at)
+            .field("user", &self.user)
+            .finish()
+    }
+}
+
+impl OAuthToken {
+    /// Create a token from an access token, for example a personal access token.
+    ///
+    /// Such a token is never refreshed.
+    pub fn new(access_token: impl Into<String>) -> Self {
+        Self {
+            access_token: access_token.into(),
+            refresh_token: None,
+            expires_at: None,
+            user: None,
+        }
+    }
+
+    /// Check if the access token is expired, according to the expiry time given by Canvas.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= SystemTime::now())
+    }
+
+    /// Check if the token should be refreshed after Canvas answered with this response.
+    pub(crate) fn needs_refresh(&self, resp: &HttpResponse) -> bool {
+        resp.status == StatusCode::UNAUTHORIZED
+            && self.refresh_token.is_some()
+            && (self.is_expired() || resp.headers.contains_key(WWW_AUTHENTICATE))
+    }
+}
+
+/// The user to which a token belongs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthUser {
+    pub id: UserId,
+    pub name: Option<String>,
+    pub global_id: Option<String>,
+}
+
+/// The response of the token endpoint.
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: Option<u64>,
+    user: Option<OAuthUser>,
+}
@@ -0,0 +1,310 @@
+//! Pagination of requests that return a list of objects.
+//!
+//! Canvas splits long lists over multiple pages.
+//! The urls of the other pages are passed in the `Link` header of the response, as described in
+//! [RFC 8288](https://www.rfc-editor.org/rfc/rfc8288).
+//!
+//! # Example
+//!
+//! ```
+//! # use canvasapi::pagination::Links;
+//! let links = Links::parse(
+//!     r#"<https://canvas.test.be/api/v1/courses?page=2&per_page=10>; rel="next",
+//!        <https://canvas.test.be/api/v1/courses?page=5&per_page=10>; rel="last""#,
+//! );
+//!
+//! assert_eq!(
+//!     links.next.as_deref(),
+//!     Some("https://canvas.test.be/api/v1/courses?page=2&per_page=10")
+//! );
+//! assert_eq!(links.last_page(), Some(5));
+//! ```
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+use url::Url;
+
+use crate::error::CanvasError;
+use crate::parameters::MASQUERADE_PARAMETER;
+use crate::requests::ResponseMetadata;
+
+/// The pagination links of a response.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Links {
+    /// The url of the current page.
+    pub current: Option<String>,
+    /// The url of the next page, or `None` on the last page.
+    pub next: Option<String>,
+    /// The url of the previous page, or `None` on the first page.
+    pub prev: Option<String>,
+    /// The url of the first page.
+    pub first: Option<String>,
+    /// The url of the last page.
+    /// Canvas omits this link when counting the pages is too expensive.
+    pub last: Option<String>,
+}
+
+impl Links {
+    /// Parse the value of a `Link` header.
+    ///
+    /// Links with other relation types are ignored.
+    /// When a relation type occurs more than once, the first link is used.
+    pub fn parse(header: &str) -> Self {
+        let mut links = Links::default();
+
+        for (uri, rels) in parse_link_values(header) {
+            for rel in rels.split_ascii_whitespace() {
+                let link = match rel.to_ascii_lowercase().as_str() {
+                    "current" => &mut links.current,
+                    "next" => &mut links.next,
+                    "prev" | "previous" => &mut links.prev,
+                    "first" => &mut links.first,
+                    "last" => &mut links.last,
+                    _ => continue,
+                };
+
+                if link.is_none() {
+                    *link = Some(uri.clone());
+                }
+            }
+        }
+
+        links
+    }
+
+    /// Parse the `Link` headers of a response.
+    ///
+    /// Relative links are resolved against the url of the response.
+    /// When the response was requested as another user, the links keep the `as_user_id` parameter.
+    pub(crate) fn from_response(
+        headers: &reqwest::header::HeaderMap,
+        url: &Url,
+    ) -> Result<Self, CanvasError> {
+        let mut links = Links::default();
+
+        for header in headers.get_all(reqwest::header::LINK) {
+            let header = header
+                .to_str()
+                .map_err(|_| CanvasError::InvalidResponse("Invalid `Link` header".into()))?;
+            links = links.or(Links::parse(header));
+        }
+
+        for uri in links.iter_mut() {
+            *uri = url
+                .join(uri)
+                .map_err(|e| CanvasError::InvalidResponse(format!("Invalid link `{uri}`: {e}")))?
+                .into();
+        }
+
+        if let Some(masquerade) = query_value(url, MASQUERADE_PARAMETER) {
+            for link in links.iter_mut() {
+                *link = keep_parameter(link, MASQUERADE_PARAMETER, &masquerade);
+            }
+        }
+
+        Ok(links)
+    }
+
+    /// The page number of the current page, when Canvas uses numbered pages.
+    pub fn current_page(&self) -> Option<usize> {
+        page_number(self.current.as_deref()?)
+    }
+
+    /// The page number of the last page, when Canvas uses numbered pages.
+    pub fn last_page(&self) -> Option<usize> {
+        page_number(self.last.as_deref()?)
+    }
+
+    /// The urls of all the pages after the current one, when Canvas uses numbered pages and
+    /// returns a link to the last page.
+    ///
+    /// The urls are created from the `next` link by replacing the page number, such that all the
+    /// other parameters are kept.
+    pub(crate) fn remaining_page_urls(&self) -> Option<Vec<String>> {
+        let next = Url::parse(self.next.as_deref()?).ok()?;
+        let next_page = page_number(next.as_str())?;
+        let last_page = self.last_page()?;
+
+        let urls = (next_page..=last_page)
+            .map(|page| {
+                let mut url = next.clone();
+                let query: Vec<(String, String)> = next
+                    .query_pairs()
+                    .map(|(name, value)| match name.as_ref() {
+                        "page" => (name.into_owned(), page.to_string()),
+                        _ => (name.into_owned(), value.into_owned()),
+                    })
+                    .collect();
+                url.query_pairs_mut().clear().extend_pairs(query);
+                url.into()
+            })
+            .collect();
+
+        Some(urls)
+    }
+
+    /// The links that are present.
+    fn iter_mut(&mut self) -> impl Iterator<Item = &mut String> {
+        [
+            &mut self.current,
+            &mut self.next,
+            &mut self.prev,
+            &mut self.first,
+            &mut self.last,
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Combine the links, preferring the links of `self`.
+    fn or(self, other: Links) -> Links {
+        Links {
+            current: self.current.or(other.current),
+            next: self.next.or(other.next),
+            prev: self.prev.or(other.prev),
+            first: self.first.or(other.first),
+            last: self.last.or(other.last),
+        }
+    }
+}
+
+/// A single page of objects.
+#[derive(Debug, Clone)]
+pub struct Page<Output> {
+    /// The objects on this page.
+    pub items: Vec<Output>,
+    /// The links to the other pages.
+    pub links: Links,
+    /// The status, headers and url of the response of this page.
+    pub metadata: ResponseMetadata,
+}
+
+/// The value of a query parameter of a url.
+fn query_value(url: &Url, name: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(n, _)| n == name)
+        .map(|(_, value)| value.into_owned())
+}
+
+/// Add the parameter to the link, when the link does not have it.
+fn keep_parameter(link: &str, name: &str, value: &str) -> String {
+    match Url::parse(link) {
+        Ok(mut url) if query_value(&url, name).is_none() => {
+            url.query_pairs_mut().append_pair(name, value);
+            url.into()
+        }
+        _ => link.to_string(),
+    }
+}
+
+/// The value of the numeric `page` parameter of a link.
+pub(crate) fn page_number(uri: &str) -> Option<usize> {
+    Url::parse(uri)
+        .ok()?
+        .query_pairs()
+        .find(|(name, _)| name == "page")?
+        .1
+        .parse()
+        .ok()
+}
+
+/// Parse the link values of a `Link` header into the target uri and the `rel` parameter.
+///
+/// Commas and semicolons are allowed in the uri and in quoted parameter values.
+fn parse_link_values(header: &str) -> Vec<(String, String)> {
+    let mut chars = header.chars().peekable();
+    let mut values = vec![];
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {}
+
+        match chars.next() {
+            Some('<') => {}
+            Some(_) => {
+                skip_link_value(&mut chars);
+                continue;
+            }
+            None => break,
+        }
+
+        let mut uri = String::new();
+        let mut closed = false;
+        for c in chars.by_ref() {
+            if c == '>' {
+                closed = true;
+                break;
+            }
+            uri.push(c);
+        }
+
+        if !closed {
+            break;
+        }
+
+        let mut rel = None;
+        loop {
+            skip_whitespace(&mut chars);
+
+            if chars.next_if_eq(&';').is_none() {
+                break;
+            }
+
+            skip_whitespace(&mut chars);
+            let name = take_token(&mut chars);
+            skip_whitespace(&mut chars);
+
+            let value = if chars.next_if_eq(&'=').is_some() {
+                skip_whitespace(&mut chars);
+                if chars.next_if_eq(&'"').is_some() {
+                    take_quoted_string(&mut chars)
+                } else {
+                    take_token(&mut chars)
+                }
+            } else {
+                String::new()
+            };
+
+            if name.eq_ignore_ascii_case("rel") && rel.is_none() {
+                rel = Some(value);
+            }
+        }
+
+        skip_link_value(&mut chars);
+
+        if let Some(rel) = rel {
+            values.push((uri.trim().to_string(), rel));
+        }
+    }
+
+    values
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+/// Skip the remainder of an invalid link value, up to the next comma.
+fn skip_link_value(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| *c != ',').is_some() {}
+}
+
+fn take_token(chars: &mut Peekable<Chars>) -> String {
+    let mut token = String::new();
+    while let Some(c) = chars.next_if(|c| !c.is_whitespace() && !matches!(c, '=' | ';' | ',')) {
+        token.push(c);
+    }
+    token
+}
+
+fn take_quoted_string(chars: &mut Peekable<Chars>) -> String {
+    let mut value = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => value.extend(chars.next()),
+            c => value.push(c),
+        }
+    }
+    value
+}
@@ -2,8 +2,12 @@
 //!
 //! # Example
 //!
-//! For example, the `EnrollmentType` is added as a parameter, which will return only the students
-//! for the specific course.
+//! Every request has setters for its parameters, which are generated for the endpoint. For
+//! example, the `enrollment_type` of `Course::get_users` is set to `EnrollmentType::Student`,
+//! which will return only the students for the specific course.
+//! Parameters that an endpoint does not support do not compile. The `include[]` values are scoped
+//! to the endpoints as well, for example `UserInclude` for the users of a course.
+//!
 //! To know which parameters can be used for a request, the official Canvas LMS API documentation
 //! needs to be consulted.
 //!
@@ -25,12 +29,32 @@
 //! let students = course
 //!     .get_users()
 //!     .unwrap()
-//!     .add_parameter(EnrollmentType::Student)
+//!     .enrollment_type(EnrollmentType::Student)
+//!     .include(UserInclude::AvatarUrl)
 //!     .fetch(&canvas).await.unwrap().inner();
 //! #   }
 //! ```
+//!
+//! A parameter of another endpoint does not compile, for example sorting the files of a course
+//! on the email address:
+//!
+//! ```compile_fail
+//! # use canvasapi::prelude::*;
+//! # fn test(course: &Course) {
+//! let files = course.get_files().unwrap().sort(SortOn::Email);
+//! # }
+//! ```
+//!
+//! Array parameters, such as `include[]`, are passed by calling the setter multiple times.
+//! Only the requests of a [`RawEndpoint`](crate::endpoint::RawEndpoint) accept any parameter,
+//! with `parameter`. The `add_parameter` and `add_parameters` methods of the other requests are
+//! deprecated in favour of the setters.
+//! The parameters are percent-encoded when the request is sent.
+
+use crate::ids::UserId;
 
 /// Parameter that can be added to a request.
+#[derive(Debug, Clone)]
 pub struct RequestParameter {
     pub name: String,
     pub value: String,
@@ -53,6 +77,7 @@ macro_rules! api_parameter {
             )*
         }
 
+        #[allow(deprecated)]
         impl From<$name> for RequestParameter {
             fn from(value: $name) -> Self {
                 RequestParameter {
@@ -64,6 +89,16 @@ macro_rules! api_parameter {
             }
         }
 
+        #[allow(deprecated)]
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(match self {
+                    $(<$name>::$option => $option_output,)*
+                })
+            }
+        }
+
+        #[allow(deprecated)]
         impl std::str::FromStr for $name {
             type Err = ();
 
@@ -94,12 +129,6 @@ macro_rules! api_parameter {
     };
 }
 
-api_parameter! {
-    /// If this parameter is given and it corresponds to a user in the course, the page parameter
-    /// will be ignored and the page containing the specified user will be returned instead.
-    UserId => "user_id"
-}
-
 api_parameter! {
     #[derive(Debug)]
     /// Parameter to specify the enrollment type of a user.
@@ -122,6 +151,14 @@ api_parameter! {
     SisId => "sis_id",
 }
 
+api_parameter! {
+    #[derive(Debug)]
+    /// Parameter to specify the action that is taken when deleting a course.
+    CourseEvent => "event",
+    Delete => "delete",
+    Conclude => "conclude",
+}
+
 api_parameter! {
     EnrollmentState => "enrollment_state[]",
     Active => "active",
@@ -133,6 +170,7 @@ api_parameter! {
 }
 
 api_parameter! {
+    #[deprecated(note = "use the include enum of the endpoint, such as `UserInclude`")]
     Include => "include[]",
     Enrollments => "enrollments",
     Locked => "locked",
@@ -160,3 +198,18 @@ api_parameter! {
     CourseImage => "course_image",
     Concluded => "concluded",
 }
+
+impl RequestParameter {
+    /// The parameter to act as another user, which Canvas calls masquerading.
+    ///
+    /// The user of the access token needs the permission to become other users.
+    pub(crate) fn masquerade(user: &UserId) -> Self {
+        RequestParameter {
+            name: MASQUERADE_PARAMETER.into(),
+            value: user.to_string(),
+        }
+    }
+}
+
+/// The name of the parameter that is used for masquerading.
+pub(crate) const MASQUERADE_PARAMETER: &str = "as_user_id";
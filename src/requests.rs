@@ -1,285 +1,1200 @@
-use anyhow::anyhow;
+use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use url::Url;
 
 use crate::canvas::CanvasInformation;
+use crate::endpoint::{Endpoint, RawEndpoint};
+use crate::error::{CanvasError, GraphQLError};
+use crate::ids::UserId;
+use crate::pagination::{Links, Page};
 use crate::parameters::*;
+use crate::transport::{header_f64, HttpResponse};
 
-pub struct GetObjectResponse<Output>(Output)
+/// The response of a request, with the metadata of the responses it was created from.
+pub struct GetObjectResponse<Output>
 where
-    Output: serde::de::DeserializeOwned;
+    Output: serde::de::DeserializeOwned,
+{
+    output: Output,
+    metadata: Vec<ResponseMetadata>,
+}
 
 impl<Output> GetObjectResponse<Output>
 where
     Output: serde::de::DeserializeOwned,
 {
+    pub(crate) fn new(output: Output, metadata: Vec<ResponseMetadata>) -> Self {
+        Self { output, metadata }
+    }
+
     pub fn inner(self) -> Output {
-        self.0
+        self.output
+    }
+
+    /// The metadata of the response, or of the last page for paged requests.
+    pub fn metadata(&self) -> &ResponseMetadata {
+        self.metadata
+            .last()
+            .expect("A response has the metadata of at least one request")
+    }
+
+    /// The metadata of every page, in the order of the pages.
+    /// For requests that are not paged, this is the metadata of the only response.
+    pub fn page_metadata(&self) -> &[ResponseMetadata] {
+        &self.metadata
+    }
+
+    /// The sum of the `X-Request-Cost` of all the responses.
+    pub fn total_request_cost(&self) -> f64 {
+        self.metadata
+            .iter()
+            .filter_map(ResponseMetadata::request_cost)
+            .sum()
+    }
+
+    /// Split the response into the object and the metadata of every response.
+    pub fn into_parts(self) -> (Output, Vec<ResponseMetadata>) {
+        (self.output, self.metadata)
+    }
+}
+
+/// The status, headers and final url of a response.
+///
+/// # Example
+/// ```no_run
+/// # use canvasapi::prelude::*;
+/// # async fn example(canvas: &CanvasInformation) -> Result<(), CanvasError> {
+/// let response = Canvas::get_todo_items()?.fetch(canvas).await?;
+/// for page in response.page_metadata() {
+///     println!("{:?} cost {:?}", page.request_context_id(), page.request_cost());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResponseMetadata {
+    /// The status of the response.
+    pub status: StatusCode,
+    /// The headers of the response.
+    pub headers: HeaderMap,
+    /// The url of the response, after following redirects.
+    pub url: Url,
+}
+
+impl ResponseMetadata {
+    /// The cost of the request, as reported in the `X-Request-Cost` header.
+    pub fn request_cost(&self) -> Option<f64> {
+        header_f64(&self.headers, "x-request-cost")
+    }
+
+    /// The remaining rate limit quota, as reported in the `X-Rate-Limit-Remaining` header.
+    pub fn rate_limit_remaining(&self) -> Option<f64> {
+        header_f64(&self.headers, "x-rate-limit-remaining")
     }
+
+    /// The id of the request, as reported in the `X-Request-Context-Id` header.
+    /// Canvas support asks for this id when investigating a request.
+    pub fn request_context_id(&self) -> Option<&str> {
+        self.headers
+            .get("x-request-context-id")
+            .and_then(|value| value.to_str().ok())
+    }
+}
+
+/// Policy for retrying requests that failed because of a transient error.
+///
+/// Only requests that can safely be sent more than once are retried: `GET`, `PUT` and `DELETE`
+/// requests. `POST` requests are never retried, as they could create an object twice.
+/// Every page of a paged request is retried on its own.
+///
+/// # Example
+/// ```
+/// # use canvasapi::prelude::*;
+/// # use std::time::Duration;
+/// let canvas = CanvasInformation::builder("https://canvas.test.be", "MY_TOKEN")
+///     .retry_policy(RetryPolicy {
+///         max_attempts: 5,
+///         initial_backoff: Duration::from_secs(1),
+///         ..RetryPolicy::default()
+///     })
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts, including the first one.
+    pub max_attempts: usize,
+    /// The backoff before the first retry.
+    pub initial_backoff: Duration,
+    /// The maximum backoff between two attempts.
+    pub max_backoff: Duration,
+    /// The factor with which the backoff grows after every attempt.
+    pub multiplier: f64,
+    /// Randomize the backoff, such that clients don't retry at the same moment.
+    /// The actual backoff is a random duration between half and the full backoff.
+    pub jitter: bool,
+    /// The response statuses that are retried.
+    pub retry_statuses: Vec<StatusCode>,
+    /// Retry requests that timed out.
+    pub retry_timeouts: bool,
+    /// Retry requests of which the connection failed or was dropped.
+    pub retry_connection_errors: bool,
 }
 
-/// An object for get requests with a single map.
-pub struct GetObjectRequestMap<Output>
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: true,
+            retry_statuses: vec![
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_timeouts: true,
+            retry_connection_errors: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn never() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Check if the method of a request allows to send it more than once.
+    pub(crate) fn applies_to(&self, method: &reqwest::Method) -> bool {
+        method != reqwest::Method::POST
+    }
+
+    /// The backoff after the given attempt, or `None` when a response with this status should
+    /// be returned.
+    pub(crate) fn retry_status(
+        &self,
+        status: StatusCode,
+        headers: &HeaderMap,
+        attempts: usize,
+    ) -> Option<Duration> {
+        if attempts >= self.max_attempts || !self.retry_statuses.contains(&status) {
+            return None;
+        }
+
+        let backoff = self.backoff(attempts);
+
+        // Respect the time the server asks to wait, when it is longer than the backoff.
+        let retry_after = headers
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(Duration::from_secs)
+            .filter(|retry_after| *retry_after <= self.max_backoff);
+
+        Some(retry_after.map_or(backoff, |retry_after| retry_after.max(backoff)))
+    }
+
+    /// The backoff after the given attempt, or `None` when the error should be returned.
+    pub(crate) fn retry_error(&self, error: &CanvasError, attempts: usize) -> Option<Duration> {
+        use std::io::ErrorKind;
+
+        if attempts >= self.max_attempts {
+            return None;
+        }
+
+        let retryable = match error {
+            CanvasError::Http(error) => {
+                // Other errors, such as an invalid request, fail again when they are retried.
+                (self.retry_timeouts && error.is_timeout())
+                    || (self.retry_connection_errors && error.is_connect())
+            }
+            CanvasError::Io(error) => match error.kind() {
+                ErrorKind::TimedOut => self.retry_timeouts,
+                ErrorKind::ConnectionRefused
+                | ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::NotConnected
+                | ErrorKind::BrokenPipe
+                | ErrorKind::UnexpectedEof => self.retry_connection_errors,
+                _ => false,
+            },
+            _ => false,
+        };
+
+        retryable.then(|| self.backoff(attempts))
+    }
+
+    /// The backoff after the given number of attempts.
+    fn backoff(&self, attempts: usize) -> Duration {
+        let exponent = attempts.saturating_sub(1).min(i32::MAX as usize) as i32;
+        let backoff = (self.initial_backoff.as_secs_f64()
+            * self.multiplier.max(1.0).powi(exponent))
+        .min(self.max_backoff.as_secs_f64());
+
+        let backoff = if self.jitter {
+            backoff * (0.5 + 0.5 * random_fraction())
+        } else {
+            backoff
+        };
+
+        Duration::from_secs_f64(backoff)
+    }
+}
+
+/// A pseudo-random number between 0 and 1, used for the jitter of the backoff.
+fn random_fraction() -> f64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos()),
+    );
+
+    hasher.finish() as f64 / u64::MAX as f64
+}
+
+/// A request of an [`Endpoint`] that returns a map, of which a single value is used.
+///
+/// Canvas returns some numbers as strings in a map, for example `{"unread_count": "3"}`, so the
+/// value is parsed after it is read from the map.
+pub struct GetObjectRequestMap<Output, E = RawEndpoint<HashMap<String, String>>>
 where
     Output: serde::de::DeserializeOwned + Clone + Copy + std::str::FromStr,
     <Output as std::str::FromStr>::Err: std::fmt::Debug,
 {
-    url: String,
-    parameters: Vec<RequestParameter>,
+    query: Query<E>,
     output: PhantomData<Output>,
     key: String,
 }
 
-impl<Output> GetObjectRequestMap<Output>
+impl<Output, E> GetObjectRequestMap<Output, E>
 where
     Output: serde::de::DeserializeOwned + Clone + Copy + std::str::FromStr,
     <Output as std::str::FromStr>::Err: std::fmt::Debug,
+    E: Endpoint<Output = HashMap<String, String>>,
 {
-    pub fn new(url: String, key: String) -> Self {
+    /// Create a request of the endpoint, that returns the value with the given key.
+    pub fn new(endpoint: E, key: impl Into<String>) -> Self {
         Self {
-            url,
-            parameters: vec![],
+            query: Query::new(endpoint),
             output: PhantomData,
-            key,
+            key: key.into(),
         }
     }
 
-    #[cfg(feature = "blocking")]
-    pub fn fetch(
+    /// The endpoint of the request, to change its parameters.
+    pub fn endpoint_mut(&mut self) -> &mut E {
+        self.query.endpoint_mut()
+    }
+
+    /// Make this request as another user, which Canvas calls masquerading.
+    ///
+    /// This takes precedence over the user of
+    /// [`CanvasInformationBuilder::masquerade`](crate::canvas::CanvasInformationBuilder::masquerade).
+    pub fn masquerade(mut self, user: impl Into<UserId>) -> Self {
+        self.query = self.query.masquerade(user);
+        self
+    }
+
+    /// Add a parameter, which can be any parameter.
+    #[deprecated(note = "use the setters of the endpoint, or `RawEndpoint::parameter`")]
+    #[allow(deprecated)]
+    pub fn add_parameter(mut self, parameter: impl Into<RequestParameter>) -> Self {
+        self.query = self.query.add_parameter(parameter);
+        self
+    }
+
+    /// Add multiple parameters, for example multiple values of an array parameter.
+    #[deprecated(note = "use the setters of the endpoint, or `RawEndpoint::parameter`")]
+    #[allow(deprecated)]
+    pub fn add_parameters<P: Into<RequestParameter>>(
         mut self,
-        canvas: &CanvasInformation<'_>,
-    ) -> anyhow::Result<GetObjectResponse<Output>> {
-        let resp: std::collections::HashMap<String, String> = canvas
-            .get_request(canvas.add_url_prefix(&self.url))
-            .send()?
-            .json()?;
-
-        let val: Output = resp[&self.key]
+        parameters: impl IntoIterator<Item = P>,
+    ) -> Self {
+        self.query = self.query.add_parameters(parameters);
+        self
+    }
+
+    pub async fn fetch(
+        self,
+        canvas: &CanvasInformation,
+    ) -> Result<GetObjectResponse<Output>, CanvasError> {
+        let (map, metadata) = self.query.fetch(canvas).await?.into_parts();
+
+        let val: Output = map
+            .get(&self.key)
+            .ok_or_else(|| CanvasError::missing_field(&self.key))?
             .parse()
-            .map_err(|_| anyhow!("Failed to parse output"))?;
+            .map_err(|_| CanvasError::InvalidResponse(format!("Failed to parse `{}`", self.key)))?;
+
+        Ok(GetObjectResponse::new(val, metadata))
+    }
+
+    /// Fetch the object, blocking the current thread until the response is received.
+    ///
+    /// # Panics
+    /// Panics when called from within an asynchronous runtime. Use `fetch` there.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_blocking(
+        self,
+        canvas: &CanvasInformation,
+    ) -> Result<GetObjectResponse<Output>, CanvasError> {
+        canvas.block_on(self.fetch(canvas))
+    }
+}
+
+/// A request of an [`Endpoint`] that returns a single object.
+///
+/// This is used for get requests of a single object, and for post, put and delete requests.
+pub struct Query<E> {
+    endpoint: E,
+    parameters: Vec<RequestParameter>,
+}
+
+impl<E> Query<E>
+where
+    E: Endpoint,
+{
+    /// Create a request of the endpoint.
+    pub fn new(endpoint: E) -> Self {
+        Self {
+            endpoint,
+            parameters: vec![],
+        }
+    }
+
+    /// The endpoint of the request.
+    pub fn endpoint(&self) -> &E {
+        &self.endpoint
+    }
+
+    /// The endpoint of the request, to change its parameters.
+    pub fn endpoint_mut(&mut self) -> &mut E {
+        &mut self.endpoint
+    }
+
+    /// Make this request as another user, which Canvas calls masquerading.
+    ///
+    /// This takes precedence over the user of
+    /// [`CanvasInformationBuilder::masquerade`](crate::canvas::CanvasInformationBuilder::masquerade).
+    pub fn masquerade(mut self, user: impl Into<UserId>) -> Self {
+        self.parameters
+            .push(RequestParameter::masquerade(&user.into()));
+        self
+    }
+
+    /// Add a parameter, which can be any parameter.
+    #[deprecated(note = "use the setters of the endpoint, or `RawEndpoint::parameter`")]
+    pub fn add_parameter(mut self, parameter: impl Into<RequestParameter>) -> Self {
+        self.parameters.push(parameter.into());
+        self
+    }
 
-        Ok(GetObjectResponse(val))
+    /// Add multiple parameters, for example multiple values of an array parameter.
+    #[deprecated(note = "use the setters of the endpoint, or `RawEndpoint::parameter`")]
+    pub fn add_parameters<P: Into<RequestParameter>>(
+        mut self,
+        parameters: impl IntoIterator<Item = P>,
+    ) -> Self {
+        self.parameters
+            .extend(parameters.into_iter().map(Into::into));
+        self
     }
 
-    #[cfg(not(feature = "blocking"))]
+    /// Send the request and deserialize the response.
     pub async fn fetch(
+        self,
+        canvas: &CanvasInformation,
+    ) -> Result<GetObjectResponse<E::Output>, CanvasError> {
+        let method = self.endpoint.method();
+        let path = self.endpoint.path();
+
+        instrument!(
+            (
+                "fetch",
+                method = method.as_str(),
+                path = self.endpoint.template().unwrap_or(&path)
+            ),
+            async move {
+                let url = request_url(
+                    canvas,
+                    &path,
+                    &endpoint_parameters(&self.endpoint, self.parameters),
+                )?;
+                let mut request = canvas.request(method, url)?;
+
+                if let Some(body) = self.endpoint.body()? {
+                    request = request.json(&body)?;
+                }
+
+                let resp = canvas.send(request).await?.error_for_status()?;
+
+                Ok(GetObjectResponse::new(resp.json()?, vec![resp.metadata()]))
+            }
+        )
+        .await
+    }
+
+    /// Send the request, blocking the current thread until the response is received.
+    ///
+    /// # Panics
+    /// Panics when called from within an asynchronous runtime. Use `fetch` there.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_blocking(
+        self,
+        canvas: &CanvasInformation,
+    ) -> Result<GetObjectResponse<E::Output>, CanvasError> {
+        canvas.block_on(self.fetch(canvas))
+    }
+}
+
+impl<Output> Query<RawEndpoint<Output>> {
+    /// Add a query parameter, which can be any parameter. Array parameters are added once for
+    /// every value.
+    pub fn parameter(mut self, parameter: impl Into<RequestParameter>) -> Self {
+        self.endpoint = self.endpoint.parameter(parameter);
+        self
+    }
+
+    /// Set the JSON body that is sent with the request.
+    pub fn body(mut self, body: impl serde::Serialize) -> Result<Self, CanvasError> {
+        self.endpoint = self.endpoint.body(body)?;
+        Ok(self)
+    }
+}
+
+/// A request of an [`Endpoint`] that returns a list of objects, over one or more pages.
+///
+/// The pages are always requested with get requests, so the method and the body of the endpoint
+/// are not used.
+pub struct PagedQuery<E> {
+    endpoint: E,
+    parameters: Vec<RequestParameter>,
+    concurrency: usize,
+}
+
+impl<E> PagedQuery<E>
+where
+    E: Endpoint,
+{
+    /// Create a paged request of the endpoint.
+    pub fn new(endpoint: E) -> Self {
+        Self {
+            endpoint,
+            parameters: vec![],
+            concurrency: 1,
+        }
+    }
+
+    /// The endpoint of the request.
+    pub fn endpoint(&self) -> &E {
+        &self.endpoint
+    }
+
+    /// The endpoint of the request, to change its parameters.
+    pub fn endpoint_mut(&mut self) -> &mut E {
+        &mut self.endpoint
+    }
+
+    /// Fetch the pages concurrently, with at most `concurrency` requests at the same time.
+    ///
+    /// This is only possible when Canvas uses numbered pages and returns a link to the last page.
+    /// Otherwise, for example when Canvas uses bookmarks, the pages are fetched one after another.
+    /// The objects are returned in the same order as when the pages are fetched one by one.
+    ///
+    /// This only applies to `fetch`. By default, the pages are fetched one after another.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Fetch all the pages and collect the objects.
+    pub async fn fetch(
+        self,
+        canvas: &CanvasInformation,
+    ) -> Result<GetObjectResponse<Vec<E::Output>>, CanvasError> {
+        use futures::{StreamExt, TryStreamExt};
+
+        let path = self.endpoint.path();
+
+        instrument!(
+            (
+                "fetch",
+                method = "GET",
+                path = self.endpoint.template().unwrap_or(&path),
+                concurrency = self.concurrency
+            ),
+            async move {
+                let first = get_page::<E::Output>(canvas, self.url(canvas)?).await?;
+                let mut output = first.items;
+                let mut metadata = vec![first.metadata];
+
+                match first.links.remaining_page_urls() {
+                    Some(urls) if self.concurrency > 1 => {
+                        let pages: Vec<Page<E::Output>> = futures::stream::iter(urls)
+                            .map(|url| get_page::<E::Output>(canvas, url))
+                            .buffered(self.concurrency)
+                            .try_collect()
+                            .await?;
+
+                        for page in pages {
+                            output.extend(page.items);
+                            metadata.push(page.metadata);
+                        }
+                    }
+                    _ => {
+                        let mut next_url = first.links.next;
+                        while let Some(url) = next_url {
+                            let page = get_page::<E::Output>(canvas, url).await?;
+                            output.extend(page.items);
+                            metadata.push(page.metadata);
+                            next_url = page.links.next;
+                        }
+                    }
+                }
+
+                event!(
+                    DEBUG,
+                    pages = metadata.len(),
+                    items = output.len(),
+                    "fetched all pages"
+                );
+
+                Ok(GetObjectResponse::new(output, metadata))
+            }
+        )
+        .await
+    }
+
+    /// Fetch all the pages and collect the objects, blocking the current thread until all the
+    /// pages are received.
+    ///
+    /// # Panics
+    /// Panics when called from within an asynchronous runtime. Use `fetch` there.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_blocking(
+        self,
+        canvas: &CanvasInformation,
+    ) -> Result<GetObjectResponse<Vec<E::Output>>, CanvasError> {
+        canvas.block_on(self.fetch(canvas))
+    }
+
+    /// Lazily fetch the objects, page by page.
+    ///
+    /// The next page is only requested when all the objects of the current page are consumed.
+    /// When a page fails, the error is returned and the iteration stops.
+    ///
+    /// ```no_run
+    /// # use canvasapi::prelude::*;
+    /// # fn test(canvas: &CanvasInformation, course: &Course) -> Result<(), CanvasError> {
+    /// for user in course.get_users()?.iter(canvas).take(10) {
+    ///     println!("{:?}", user?.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn iter(self, canvas: &CanvasInformation) -> PagedObjectIter<E::Output> {
+        PagedObjectIter {
+            canvas: canvas.clone(),
+            next_url: Some(self.url(canvas)),
+            page: std::collections::VecDeque::new(),
+        }
+    }
+
+    /// Lazily fetch the objects, page by page.
+    ///
+    /// The next page is only requested when all the objects of the current page are consumed.
+    /// When a page fails, the error is returned and the stream ends.
+    ///
+    /// ```no_run
+    /// # use canvasapi::prelude::*;
+    /// # async fn test(canvas: &CanvasInformation, course: &Course) -> Result<(), CanvasError> {
+    /// use futures::{StreamExt, TryStreamExt};
+    ///
+    /// let mut users = std::pin::pin!(course.get_users()?.stream(canvas).take(10));
+    /// while let Some(user) = users.try_next().await? {
+    ///     println!("{:?}", user.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream(
+        self,
+        canvas: &CanvasInformation,
+    ) -> impl futures::Stream<Item = Result<E::Output, CanvasError>> {
+        use futures::TryStreamExt;
+
+        let canvas = canvas.clone();
+        let first_url = self.url(&canvas);
+
+        futures::stream::try_unfold(Some(first_url), move |url| {
+            let canvas = canvas.clone();
+            async move {
+                let url = match url {
+                    Some(url) => url?,
+                    None => return Ok::<_, CanvasError>(None),
+                };
+
+                let page = get_page::<E::Output>(&canvas, url).await?;
+
+                Ok(Some((page.items, page.links.next.map(Ok))))
+            }
+        })
+        .map_ok(|page| futures::stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    /// Make this request as another user, which Canvas calls masquerading.
+    ///
+    /// This takes precedence over the user of
+    /// [`CanvasInformationBuilder::masquerade`](crate::canvas::CanvasInformationBuilder::masquerade).
+    pub fn masquerade(mut self, user: impl Into<UserId>) -> Self {
+        self.parameters
+            .push(RequestParameter::masquerade(&user.into()));
+        self
+    }
+
+    /// Add a parameter, which can be any parameter.
+    #[deprecated(note = "use the setters of the endpoint, or `RawEndpoint::parameter`")]
+    pub fn add_parameter(mut self, parameter: impl Into<RequestParameter>) -> Self {
+        self.parameters.push(parameter.into());
+        self
+    }
+
+    /// Add multiple parameters, for example multiple values of an array parameter.
+    #[deprecated(note = "use the setters of the endpoint, or `RawEndpoint::parameter`")]
+    pub fn add_parameters<P: Into<RequestParameter>>(
         mut self,
-        canvas: &CanvasInformation<'_>,
-    ) -> anyhow::Result<GetObjectResponse<Output>> {
-        let resp: std::collections::HashMap<String, String> = canvas
-            .get_request(canvas.add_url_prefix(&self.url))
-            .send()
-            .await?
-            .json()
-            .await?;
-
-        let val: Output = resp[&self.key]
-            .parse()
-            .map_err(|_| anyhow!("Failed to parse output"))?;
+        parameters: impl IntoIterator<Item = P>,
+    ) -> Self {
+        self.parameters
+            .extend(parameters.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the number of objects that Canvas returns per page.
+    pub fn per_page(mut self, per_page: usize) -> Self {
+        self.parameters.push(RequestParameter {
+            name: "per_page".into(),
+            value: per_page.to_string(),
+        });
+        self
+    }
+
+    /// Fetch a single page, counting from 1.
+    ///
+    /// This only works for requests of which Canvas uses numbered pages.
+    /// The links of the page can be used to navigate to the other pages.
+    ///
+    /// ```no_run
+    /// # use canvasapi::prelude::*;
+    /// # async fn test(canvas: &CanvasInformation, course: &Course) -> Result<(), CanvasError> {
+    /// let page = course.get_users()?.per_page(20).fetch_page(canvas, 2).await?;
+    /// println!("Page 2 of {:?}", page.links.last_page());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_page(
+        self,
+        canvas: &CanvasInformation,
+        page: usize,
+    ) -> Result<Page<E::Output>, CanvasError> {
+        get_page(canvas, self.page_url(canvas, page)?).await
+    }
+
+    /// Fetch a single page, counting from 1, blocking the current thread until the page is
+    /// received.
+    ///
+    /// # Panics
+    /// Panics when called from within an asynchronous runtime. Use `fetch_page` there.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_page_blocking(
+        self,
+        canvas: &CanvasInformation,
+        page: usize,
+    ) -> Result<Page<E::Output>, CanvasError> {
+        canvas.block_on(self.fetch_page(canvas, page))
+    }
+
+    /// The url of the first page.
+    fn url(&self, canvas: &CanvasInformation) -> Result<String, CanvasError> {
+        request_url(
+            canvas,
+            &self.endpoint.path(),
+            &endpoint_parameters(&self.endpoint, self.parameters.clone()),
+        )
+    }
 
-        Ok(GetObjectResponse(val))
+    /// The url of the page with the given number.
+    fn page_url(mut self, canvas: &CanvasInformation, page: usize) -> Result<String, CanvasError> {
+        self.parameters.retain(|parameter| parameter.name != "page");
+        self.parameters.push(RequestParameter {
+            name: "page".into(),
+            value: page.to_string(),
+        });
+
+        self.url(canvas)
+    }
+}
+
+impl<Output> PagedQuery<RawEndpoint<Output>> {
+    /// Add a query parameter, which can be any parameter. Array parameters are added once for
+    /// every value.
+    pub fn parameter(mut self, parameter: impl Into<RequestParameter>) -> Self {
+        self.endpoint = self.endpoint.parameter(parameter);
+        self
     }
 }
 
-/// An object for get requests.
-pub struct GetObjectRequest<Output>
+/// The parameters of the endpoint, followed by the parameters that were added to the request.
+fn endpoint_parameters<E: Endpoint>(
+    endpoint: &E,
+    parameters: Vec<RequestParameter>,
+) -> Vec<RequestParameter> {
+    let mut all = endpoint.parameters();
+    all.extend(parameters);
+    all
+}
+
+/// Fetch a single page of objects.
+async fn get_page<Output>(
+    canvas: &CanvasInformation,
+    url: String,
+) -> Result<Page<Output>, CanvasError>
 where
     Output: serde::de::DeserializeOwned,
 {
-    url: String,
-    parameters: Vec<RequestParameter>,
+    let request = canvas.get_request(url)?;
+
+    instrument!(
+        (
+            "page",
+            page =
+                crate::pagination::page_number(request.url.as_str()).map_or(1, |page| page as u64)
+        ),
+        async move {
+            let resp = canvas.send(request).await?.error_for_status()?;
+
+            Ok(Page {
+                links: Links::from_response(&resp.headers, &resp.url)?,
+                items: resp.json()?,
+                metadata: resp.metadata(),
+            })
+        }
+    )
+    .await
+}
+
+/// Iterator that lazily fetches the objects of a paged request.
+///
+/// Created with [`PagedQuery::iter`].
+#[cfg(feature = "blocking")]
+pub struct PagedObjectIter<Output> {
+    canvas: CanvasInformation,
+    next_url: Option<Result<String, CanvasError>>,
+    page: std::collections::VecDeque<Output>,
+}
+
+#[cfg(feature = "blocking")]
+impl<Output> Iterator for PagedObjectIter<Output>
+where
+    Output: serde::de::DeserializeOwned,
+{
+    type Item = Result<Output, CanvasError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(output) = self.page.pop_front() {
+                return Some(Ok(output));
+            }
+
+            let url = match self.next_url.take()? {
+                Ok(url) => url,
+                Err(e) => return Some(Err(e)),
+            };
+
+            match self.canvas.block_on(get_page::<Output>(&self.canvas, url)) {
+                Ok(page) => {
+                    self.page.extend(page.items);
+                    self.next_url = page.links.next.map(Ok);
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// A query to the GraphQL API of Canvas.
+///
+/// The query is posted to `/api/graphql`, and the `data` of the response is deserialized into the
+/// output. When Canvas returns `errors`, the request fails with [`CanvasError::GraphQL`].
+///
+/// ```no_run
+/// # use canvasapi::prelude::*;
+/// # async fn test(canvas: &CanvasInformation) -> Result<(), CanvasError> {
+/// #[derive(serde::Deserialize)]
+/// struct Data {
+///     course: Option<CourseName>,
+/// }
+///
+/// #[derive(serde::Deserialize)]
+/// struct CourseName {
+///     name: String,
+/// }
+///
+/// let data = GraphQLRequest::<Data>::new("query($id: ID!) { course(id: $id) { name } }")
+///     .variable("id", "1")?
+///     .fetch(canvas)
+///     .await?
+///     .inner();
+/// # Ok(())
+/// # }
+/// ```
+pub struct GraphQLRequest<Output>
+where
+    Output: serde::de::DeserializeOwned,
+{
+    query: GraphQLQuery,
     output: PhantomData<Output>,
 }
 
-impl<Output> GetObjectRequest<Output>
+impl<Output> GraphQLRequest<Output>
 where
     Output: serde::de::DeserializeOwned,
 {
-    /// Create a new get request object with an correct url.
-    pub fn new(url: String) -> Self {
+    /// Create a request for the given query.
+    pub fn new(query: impl Into<String>) -> Self {
         Self {
-            url,
-            parameters: vec![],
+            query: GraphQLQuery::new(query.into()),
             output: PhantomData,
         }
     }
 
-    #[cfg(feature = "blocking")]
-    pub fn fetch(
+    /// Set the value of a variable of the query.
+    pub fn variable(
         mut self,
-        canvas: &CanvasInformation<'_>,
-    ) -> anyhow::Result<GetObjectResponse<Output>> {
-        let resp = canvas
-            .get_request(canvas.add_url_prefix(&self.url))
-            .send()?
-            .json::<Output>()?;
-
-        Ok(GetObjectResponse(resp))
+        name: impl Into<String>,
+        value: impl serde::Serialize,
+    ) -> Result<Self, CanvasError> {
+        self.query.set_variable(name.into(), value)?;
+        Ok(self)
     }
 
-    #[cfg(not(feature = "blocking"))]
     pub async fn fetch(
-        mut self,
-        canvas: &CanvasInformation<'_>,
-    ) -> anyhow::Result<GetObjectResponse<Output>> {
-        let resp = canvas
-            .get_request(canvas.add_url_prefix(&self.url))
-            .send()
-            .await?
-            .json::<Output>()
-            .await?;
+        self,
+        canvas: &CanvasInformation,
+    ) -> Result<GetObjectResponse<Output>, CanvasError> {
+        let (data, resp) = self.query.send(canvas).await?;
 
-        Ok(GetObjectResponse(resp))
+        Ok(GetObjectResponse::new(
+            from_value(data, &resp.body)?,
+            vec![resp.metadata()],
+        ))
+    }
+
+    /// Send the query, blocking the current thread until the response is received.
+    ///
+    /// # Panics
+    /// Panics when called from within an asynchronous runtime. Use `fetch` there.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_blocking(
+        self,
+        canvas: &CanvasInformation,
+    ) -> Result<GetObjectResponse<Output>, CanvasError> {
+        canvas.block_on(self.fetch(canvas))
     }
 }
 
-pub struct GetPagedObjectRequest<Output>
+/// A query to the GraphQL API of Canvas that returns a Relay-style connection.
+///
+/// The connection is found in the `data` of the response by its path, for example
+/// `course.submissionsConnection`. The nodes of the connection, either in `nodes` or in
+/// `edges { node }`, are collected. While `pageInfo { hasNextPage }` is true, the query is sent
+/// again with the `endCursor` as the value of the `cursor` variable.
+///
+/// ```no_run
+/// # use canvasapi::prelude::*;
+/// # async fn test(canvas: &CanvasInformation) -> Result<(), CanvasError> {
+/// #[derive(serde::Deserialize)]
+/// struct Submission {
+///     score: Option<f64>,
+/// }
+///
+/// let submissions = GraphQLPagedRequest::<Submission>::new(
+///     "query($id: ID!, $cursor: String) {
+///         course(id: $id) {
+///             submissionsConnection(after: $cursor) {
+///                 nodes { score }
+///                 pageInfo { hasNextPage endCursor }
+///             }
+///         }
+///     }",
+///     "course.submissionsConnection",
+/// )
+/// .variable("id", "1")?
+/// .fetch(canvas)
+/// .await?
+/// .inner();
+/// # Ok(())
+/// # }
+/// ```
+pub struct GraphQLPagedRequest<Output>
 where
     Output: serde::de::DeserializeOwned,
 {
-    url: String,
-    parameters: Vec<RequestParameter>,
+    query: GraphQLQuery,
+    connection: String,
+    cursor_variable: String,
     output: PhantomData<Vec<Output>>,
 }
 
-impl<Output> GetPagedObjectRequest<Output>
+impl<Output> GraphQLPagedRequest<Output>
 where
-    Output: serde::de::DeserializeOwned + std::fmt::Debug,
+    Output: serde::de::DeserializeOwned,
 {
-    pub fn new(url: String) -> Self {
+    /// Create a request for the given query, with the dot-separated path of the connection in
+    /// the `data` of the response.
+    pub fn new(query: impl Into<String>, connection: impl Into<String>) -> Self {
         Self {
-            url,
-            parameters: vec![],
+            query: GraphQLQuery::new(query.into()),
+            connection: connection.into(),
+            cursor_variable: "cursor".into(),
             output: PhantomData,
         }
     }
 
-    #[cfg(feature = "blocking")]
-    pub fn fetch(
+    /// Set the value of a variable of the query.
+    pub fn variable(
         mut self,
-        canvas: &CanvasInformation<'_>,
-    ) -> anyhow::Result<GetObjectResponse<Vec<Output>>> {
-        let mut output: Vec<Output> = vec![];
-        let mut url: String = canvas.add_url_prefix(&self.url);
-
-        if self.parameters.len() > 0 {
-            url = format!("{}?", url);
-            let mut first = true;
-            for parameter in &self.parameters {
-                if first {
-                    let name = &parameter.name;
-                    let value = &parameter.value;
-                    url = format!("{url}{name}={value}");
-                    first = false;
-                } else {
-                    let name = &parameter.name;
-                    let value = &parameter.value;
-                    url = format!("{url}&{name}={value}");
-                }
-            }
-        }
+        name: impl Into<String>,
+        value: impl serde::Serialize,
+    ) -> Result<Self, CanvasError> {
+        self.query.set_variable(name.into(), value)?;
+        Ok(self)
+    }
+
+    /// Set the name of the variable that is used for the cursor.
+    ///
+    /// By default, the variable is called `cursor`.
+    pub fn cursor_variable(mut self, name: impl Into<String>) -> Self {
+        self.cursor_variable = name.into();
+        self
+    }
+
+    /// Fetch all the pages and collect the nodes.
+    pub async fn fetch(
+        mut self,
+        canvas: &CanvasInformation,
+    ) -> Result<GetObjectResponse<Vec<Output>>, CanvasError> {
+        let mut output = vec![];
+        let mut metadata = vec![];
 
         loop {
-            let mut client = canvas.get_request(url);
+            let (data, resp) = self.query.send(canvas).await?;
+            let connection: Connection<Output> =
+                from_value(self.find_connection(data)?, &resp.body)?;
 
-            let mut resp = client.send()?;
-            let headers = resp.headers().clone();
+            let (nodes, page_info) = connection.into_parts();
+            output.extend(nodes);
+            metadata.push(resp.metadata());
 
-            let next_url = get_next_url(&headers)?;
+            match page_info {
+                PageInfo {
+                    has_next_page: true,
+                    end_cursor: Some(cursor),
+                } => self
+                    .query
+                    .set_variable(self.cursor_variable.clone(), cursor)?,
+                _ => break,
+            }
+        }
 
-            output.extend(resp.json::<Vec<Output>>()?);
+        Ok(GetObjectResponse::new(output, metadata))
+    }
 
-            url = if next_url.is_none() {
-                break;
-            } else {
-                next_url.unwrap().to_string()
+    /// Fetch all the pages and collect the nodes, blocking the current thread until all the
+    /// pages are received.
+    ///
+    /// # Panics
+    /// Panics when called from within an asynchronous runtime. Use `fetch` there.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_blocking(
+        self,
+        canvas: &CanvasInformation,
+    ) -> Result<GetObjectResponse<Vec<Output>>, CanvasError> {
+        canvas.block_on(self.fetch(canvas))
+    }
+
+    /// Take the connection out of the data of the response.
+    fn find_connection(
+        &self,
+        mut data: serde_json::Value,
+    ) -> Result<serde_json::Value, CanvasError> {
+        for field in self.connection.split('.') {
+            data = match data.get_mut(field) {
+                Some(value) if !value.is_null() => value.take(),
+                _ => return Err(CanvasError::missing_field(&self.connection)),
             };
         }
 
-        Ok(GetObjectResponse(output))
+        Ok(data)
     }
+}
 
-    #[cfg(not(feature = "blocking"))]
-    pub async fn fetch(
-        mut self,
-        canvas: &CanvasInformation<'_>,
-    ) -> anyhow::Result<GetObjectResponse<Vec<Output>>> {
-        use anyhow::bail;
-        use reqwest::StatusCode;
-
-        let mut output: Vec<Output> = vec![];
-        let mut url: String = canvas.add_url_prefix(&self.url);
-
-        if !self.parameters.is_empty() {
-            url = format!("{}?", url);
-            let mut first = true;
-            for parameter in &self.parameters {
-                if first {
-                    let name = &parameter.name;
-                    let value = &parameter.value;
-                    url = format!("{url}{name}={value}");
-                    first = false;
-                } else {
-                    let name = &parameter.name;
-                    let value = &parameter.value;
-                    url = format!("{url}&{name}={value}");
-                }
-            }
+/// The body of a GraphQL request.
+#[derive(serde::Serialize)]
+struct GraphQLQuery {
+    query: String,
+    variables: serde_json::Map<String, serde_json::Value>,
+}
+
+impl GraphQLQuery {
+    fn new(query: String) -> Self {
+        Self {
+            query,
+            variables: serde_json::Map::new(),
         }
+    }
 
-        loop {
-            let mut client = canvas.get_request(url);
+    fn set_variable(
+        &mut self,
+        name: String,
+        value: impl serde::Serialize,
+    ) -> Result<(), CanvasError> {
+        let value = serde_json::to_value(value).map_err(CanvasError::Serialize)?;
+        self.variables.insert(name, value);
+        Ok(())
+    }
 
-            let mut resp = client.send().await?;
+    /// Post the query, and return the `data` of the response.
+    async fn send(
+        &self,
+        canvas: &CanvasInformation,
+    ) -> Result<(serde_json::Value, HttpResponse), CanvasError> {
+        let body = serde_json::to_value(self).map_err(CanvasError::Serialize)?;
+        let url = parameterized_url(canvas, &canvas.graphql_url(), &[])?;
+        let request = canvas.request(reqwest::Method::POST, url)?.json(&body)?;
+        let resp = canvas.send(request).await?.error_for_status()?;
 
-            match resp.status() {
-                StatusCode::OK => {},
-                status => bail!("{status}"),
-            }
+        let response: GraphQLResponse = resp.json()?;
+        if !response.errors.is_empty() {
+            return Err(CanvasError::GraphQL(response.errors));
+        }
 
-            let headers = resp.headers().clone();
+        let data = response
+            .data
+            .filter(|data| !data.is_null())
+            .ok_or_else(|| CanvasError::missing_field("data"))?;
 
-            let next_url = get_next_url(&headers)?;
+        Ok((data, resp))
+    }
+}
 
-            output.extend(resp.json::<Vec<Output>>().await?);
+/// The response of the GraphQL API.
+#[derive(serde::Deserialize)]
+struct GraphQLResponse {
+    data: Option<serde_json::Value>,
+    #[serde(default)]
+    errors: Vec<GraphQLError>,
+}
 
-            url = if next_url.is_none() {
-                break;
-            } else {
-                next_url.unwrap().to_string()
-            };
-        }
+/// A page of a Relay-style connection.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Connection<Output> {
+    nodes: Option<Vec<Output>>,
+    edges: Option<Vec<Edge<Output>>>,
+    page_info: PageInfo,
+}
 
-        Ok(GetObjectResponse(output))
-    }
+impl<Output> Connection<Output> {
+    /// The nodes of the page, from `nodes` or from `edges`, and the page info.
+    fn into_parts(self) -> (Vec<Output>, PageInfo) {
+        let nodes = match (self.nodes, self.edges) {
+            (Some(nodes), _) => nodes,
+            (None, Some(edges)) => edges.into_iter().map(|edge| edge.node).collect(),
+            (None, None) => vec![],
+        };
 
-    pub fn add_parameter(mut self, parameter: impl Into<RequestParameter>) -> Self {
-        self.parameters.push(parameter.into());
-        self
+        (nodes, self.page_info)
     }
 }
 
-/// Get the next url for paging from the header information.
-fn get_next_url(resp: &reqwest::header::HeaderMap) -> anyhow::Result<Option<&str>> {
-    let headers = resp.get("link");
-    if let Some(headers) = headers {
-        let headers = headers.to_str()?;
-        let headers: Vec<&str> = headers.split(',').map(|x| x.trim()).collect();
+#[derive(serde::Deserialize)]
+struct Edge<Output> {
+    node: Output,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
 
-        for header in headers {
-            if header.contains("next") {
-                return Ok(Some(
-                    header
-                        .split(';')
-                        .map(|x| &x[1..x.len() - 1])
-                        .collect::<Vec<&str>>()[0],
-                ));
-            }
+/// Deserialize a part of the JSON body of a response.
+fn from_value<T: serde::de::DeserializeOwned>(
+    value: serde_json::Value,
+    body: &[u8],
+) -> Result<T, CanvasError> {
+    serde_path_to_error::deserialize(value).map_err(|error| CanvasError::deserialize(body, error))
+}
+
+/// Create the full url of a request, with the parameters as percent-encoded query.
+///
+/// Parameters with the same name are repeated, which is how Canvas expects array parameters such
+/// as `include[]`. The masquerade of the Canvas information is added, unless the request has its
+/// own.
+fn request_url(
+    canvas: &CanvasInformation,
+    path: &str,
+    parameters: &[RequestParameter],
+) -> Result<String, CanvasError> {
+    parameterized_url(canvas, &canvas.add_url_prefix(path), parameters)
+}
+
+/// Add the parameters, and the masquerade of the Canvas information, to a full url.
+fn parameterized_url(
+    canvas: &CanvasInformation,
+    url: &str,
+    parameters: &[RequestParameter],
+) -> Result<String, CanvasError> {
+    let mut url = Url::parse(url)
+        .map_err(|e| CanvasError::InvalidRequest(format!("Invalid url `{url}`: {e}")))?;
+
+    if !parameters.is_empty() {
+        let mut query = url.query_pairs_mut();
+        for parameter in parameters {
+            query.append_pair(&parameter.name, &parameter.value);
         }
+    }
 
-        Ok(None)
-    } else {
-        Ok(None)
+    if let Some(masquerade) = canvas.masquerade() {
+        if !parameters
+            .iter()
+            .any(|parameter| parameter.name == MASQUERADE_PARAMETER)
+        {
+            let parameter = RequestParameter::masquerade(masquerade);
+            url.query_pairs_mut()
+                .append_pair(&parameter.name, &parameter.value);
+        }
     }
+
+    Ok(url.into())
 }
 
 macro_rules! api_todo {
@@ -302,8 +1217,9 @@ macro_rules! feature {
     };
 }
 
-macro_rules! api_get {
+macro_rules! api_write {
     (
+        $method:expr,
         $(#[$outer:meta])*
         $name:ident ($($self:ident)?):
             $path:expr =>
@@ -311,7 +1227,7 @@ macro_rules! api_get {
             -> ($($path_val:ident: $path_ty:ty),*)
             -> $ret_ty:ident
             $([$($param_val:expr),*])?
-            $(features = [$($feature_name:expr),+])?
+            $(features = [$(( name = $feature_name:expr, reason = $feature_reason:expr )),+])?
     ) => {
         $(#[$outer])*
         $(
@@ -319,72 +1235,35 @@ macro_rules! api_get {
             #[doc = "\nThe `"]
             #[doc = $feature_name]
             #[doc = "` feature is needed to use this function."]
+            #[doc = $feature_reason]
             #[cfg(feature = $feature_name)]
             )*
         )?
-        pub fn $name($(&$self,)? $($path_val:$path_ty,)*) -> anyhow::Result<GetObjectRequest<$ret_ty>> {
-            Ok(GetObjectRequest::<$ret_ty>::new(
-                format!($path
-                    $(,$named_self_arg=$named_self_val)*
-                    $(,$path_val=$path_val)*))
-            $($(.add_parameter($param_val))*)?)
+        pub fn $name($(&$self,)? $($path_val:$path_ty,)*) -> Result<crate::endpoint::Query<crate::endpoint::RawEndpoint<$ret_ty>>, crate::error::CanvasError> {
+            Ok(crate::endpoint::Query::new(
+                crate::endpoint::RawEndpoint::new($method, format!($path
+                    $(,$named_self_arg=crate::endpoint::PathSegment(&$named_self_val))*
+                    $(,$path_val=crate::endpoint::PathSegment(&$path_val))*))
+                .template($path))
+            $($(.parameter($param_val))*)?)
         }
     };
+}
 
-    (
-        $(#[$outer:meta])*
-        $name:ident ($($self:ident)?):
-            $path:expr =>
-            ($($named_self_arg:ident : $named_self_val:expr),* $(,)?)
-            -> ($($path_val:ident: $path_ty:ty),*)
-            -> {$ret_name_field:literal :$ret_ty:ty}
-            $([$($param_val:expr),*])?
-            $(features = [$($feature_name:expr),+])?
-    ) => {
-        $(#[$outer])*
-        $(
-            $(
-            #[doc = "\nThe `"]
-            #[doc = $feature_name]
-            #[doc = "` feature is needed to use this function."]
-            #[cfg(feature = $feature_name)]
-            )*
-        )?
-        pub fn $name($(&$self,)? $($path_val:$path_ty,)*) -> anyhow::Result<GetObjectRequestMap<$ret_ty>> {
-            Ok(GetObjectRequestMap::<$ret_ty>::new(
-                format!($path
-                    $(,$named_self_arg=$named_self_val)*
-                    $(,$path_val=$path_val)*), $ret_name_field.into())
-            $($(.add_parameter($param_val))*)?)
-        }
+macro_rules! api_post {
+    ($($tokens:tt)*) => {
+        api_write! { reqwest::Method::POST, $($tokens)* }
     };
+}
 
-    (
-        $(#[$outer:meta])*
-        $name:ident ($($self:ident)?):
-            $path:expr =>
-            ($($named_self_arg:ident : $named_self_val:expr),* $(,)?)
-            -> ($($path_val:ident: $path_ty:ty),*)
-            -> [$ret_ty:ty]
-            $([$($param_val:expr),*])?
-            $(features = [$(( name = $feature_name:expr, reason = $feature_reason:expr )),+])?
-    ) => {
-        $(#[$outer])*
-        $(
-            $(
-            #[doc = "\nThe `"]
-            #[doc = $feature_name]
-            #[doc = "` feature is needed to use this function."]
-            #[doc = $feature_reason]
-            #[cfg(feature = $feature_name)]
-            )*
-        )?
-        pub fn $name($(&$self,)? $($path_val:$path_ty,)*) -> anyhow::Result<GetPagedObjectRequest<$ret_ty>> {
-            Ok(GetPagedObjectRequest::<$ret_ty>::new(
-                format!($path
-                    $(,$named_self_arg=$named_self_val)*
-                    $(,$path_val=$path_val)*))
-            $($(.add_parameter($param_val))*)?)
-        }
+macro_rules! api_put {
+    ($($tokens:tt)*) => {
+        api_write! { reqwest::Method::PUT, $($tokens)* }
+    };
+}
+
+macro_rules! api_delete {
+    ($($tokens:tt)*) => {
+        api_write! { reqwest::Method::DELETE, $($tokens)* }
     };
 }
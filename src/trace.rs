@@ -0,0 +1,40 @@
+//! Instrumentation of the requests with [tracing](https://crates.io/crates/tracing).
+//!
+//! The macros expand to nothing when the `tracing` feature is disabled, such that the
+//! instrumentation has no cost then.
+//! The access token is never recorded: the spans and events only contain the method, the path
+//! and the metadata of the responses.
+
+/// Run a future inside a `DEBUG` span.
+///
+/// The span is created before the future, such that the fields can borrow values that are moved
+/// into the future.
+#[cfg(feature = "tracing")]
+macro_rules! instrument {
+    (($($span:tt)+), $future:expr) => {{
+        let span = tracing::debug_span!($($span)+);
+        tracing::Instrument::instrument($future, span)
+    }};
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! instrument {
+    (($($span:tt)+), $future:expr) => {
+        $future
+    };
+}
+
+/// Emit an event at the given level, for example `event!(DEBUG, status = 200, "response")`.
+#[cfg(feature = "tracing")]
+macro_rules! event {
+    ($level:ident, $($event:tt)+) => {
+        tracing::event!(tracing::Level::$level, $($event)+)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! event {
+    ($level:ident, $($event:tt)+) => {
+        ()
+    };
+}
@@ -0,0 +1,191 @@
+//! The transport that sends the requests to Canvas.
+//!
+//! By default, the requests are sent with [reqwest](https://crates.io/crates/reqwest), using the
+//! [`ReqwestTransport`].
+//! Another transport can be used by implementing the [`Transport`] trait, for example to run the
+//! requests over an in-memory fake in tests, or to pass them through your own HTTP middleware.
+//!
+//! The throttling, retries and error handling of [`CanvasInformation`](crate::canvas::CanvasInformation)
+//! are applied on top of the transport.
+//!
+//! # Example
+//!
+//! ```
+//! # use canvasapi::prelude::*;
+//! use canvasapi::transport::{HttpRequest, HttpResponse, Transport};
+//! use futures::future::BoxFuture;
+//!
+//! /// A transport that answers every request with an empty list.
+//! struct EmptyTransport;
+//!
+//! impl Transport for EmptyTransport {
+//!     fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, CanvasError>> {
+//!         Box::pin(async move {
+//!             Ok(HttpResponse {
+//!                 status: reqwest::StatusCode::OK,
+//!                 headers: Default::default(),
+//!                 url: request.url,
+//!                 body: "[]".into(),
+//!             })
+//!         })
+//!     }
+//! }
+//!
+//! let canvas = CanvasInformation::builder("https://canvas.test.be", "MY_TOKEN")
+//!     .transport(EmptyTransport)
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use futures::future::BoxFuture;
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{Method, StatusCode};
+use url::Url;
+
+use crate::error::CanvasError;
+use crate::requests::ResponseMetadata;
+
+/// Sends a request and reads the complete response.
+///
+/// The transport only reports errors that prevented a response from being received.
+/// Unsuccessful responses, such as `404 Not Found`, are returned as a response.
+/// Errors that are returned as [`CanvasError::Io`] with the kind `TimedOut`, or with a kind that
+/// signals a failed connection, are retried according to the
+/// [`RetryPolicy`](crate::prelude::RetryPolicy).
+/// Other errors can be returned as [`CanvasError::Transport`].
+pub trait Transport: Send + Sync + 'static {
+    /// Send the request and read the complete response.
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, CanvasError>>;
+}
+
+/// A request to Canvas.
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    /// The method of the request.
+    pub method: Method,
+    /// The full url of the request, including the query.
+    pub url: Url,
+    /// The headers of the request, including the `Authorization` header with the access token.
+    pub headers: HeaderMap,
+    /// The body of the request.
+    pub body: Option<bytes::Bytes>,
+}
+
+impl HttpRequest {
+    /// Set a JSON body.
+    pub(crate) fn json(mut self, body: &serde_json::Value) -> Result<Self, CanvasError> {
+        self.headers.insert(
+            reqwest::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        self.body = Some(
+            serde_json::to_vec(body)
+                .map_err(CanvasError::Serialize)?
+                .into(),
+        );
+        Ok(self)
+    }
+}
+
+/// A response of which the body is completely read.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// The status of the response.
+    pub status: StatusCode,
+    /// The headers of the response.
+    pub headers: HeaderMap,
+    /// The url of the response, after following redirects.
+    /// Relative pagination links are resolved against this url.
+    pub url: Url,
+    /// The body of the response.
+    pub body: bytes::Bytes,
+}
+
+impl HttpResponse {
+    /// Deserialize the JSON body of the response.
+    pub(crate) fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T, CanvasError> {
+        let deserializer = &mut serde_json::Deserializer::from_slice(&self.body);
+        serde_path_to_error::deserialize(deserializer)
+            .map_err(|error| CanvasError::deserialize(&self.body, error))
+    }
+
+    /// Turn an unsuccessful response into an error.
+    pub(crate) fn error_for_status(self) -> Result<Self, CanvasError> {
+        if self.status.is_success() {
+            Ok(self)
+        } else {
+            Err(CanvasError::from_response(&self))
+        }
+    }
+
+    /// The remaining rate limit quota, as reported by Canvas.
+    pub(crate) fn rate_limit_remaining(&self) -> Option<f64> {
+        header_f64(&self.headers, "x-rate-limit-remaining")
+    }
+
+    /// The metadata of the response.
+    pub(crate) fn metadata(&self) -> ResponseMetadata {
+        ResponseMetadata {
+            status: self.status,
+            headers: self.headers.clone(),
+            url: self.url.clone(),
+        }
+    }
+
+    /// Check if the request was refused because the rate limit quota is used up.
+    pub(crate) fn is_rate_limited(&self) -> bool {
+        match self.status {
+            StatusCode::TOO_MANY_REQUESTS => true,
+            StatusCode::FORBIDDEN => {
+                self.rate_limit_remaining().is_some_and(|r| r <= 0.0)
+                    || String::from_utf8_lossy(&self.body).contains("Rate Limit Exceeded")
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The default transport, which sends the requests with a `reqwest` client.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Create a transport that sends the requests with the given client.
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, CanvasError>> {
+        Box::pin(async move {
+            let mut builder = self
+                .client
+                .request(request.method, request.url)
+                .headers(request.headers);
+
+            if let Some(body) = request.body {
+                builder = builder.body(body);
+            }
+
+            let resp = builder.send().await?;
+
+            Ok(HttpResponse {
+                status: resp.status(),
+                headers: resp.headers().clone(),
+                url: resp.url().clone(),
+                body: resp.bytes().await?,
+            })
+        })
+    }
+}
+
+/// Parse the value of a numeric header.
+pub(crate) fn header_f64(headers: &HeaderMap, name: &str) -> Option<f64> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<f64>().ok())
+}
@@ -0,0 +1,370 @@
+extern crate canvasapi;
+use canvasapi::cache::Cache;
+use canvasapi::prelude::*;
+use canvasapi::transport::{HttpRequest, HttpResponse, Transport};
+use futures::future::BoxFuture;
+use std::time::Duration;
+
+mod common;
+use common::{FakeCanvas, Response};
+
+/// Serve course 1 with an ETag, and answer the revalidation with `304 Not Modified`.
+fn serve_course(server: &FakeCanvas) {
+    server
+        .get(
+            "/api/v1/courses/1",
+            server.fixture("course.json").header("ETag", "\"v1\""),
+        )
+        .get(
+            "/api/v1/courses/1",
+            Response::json("").status(304).header("ETag", "\"v1\""),
+        );
+}
+
+/// A cache directory for a test, which does not exist yet.
+fn cache_dir(test: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("canvasapi-{}-{test}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+async fn get_course(canvas: &CanvasInformation) -> Course {
+    Canvas::get_course(1)
+        .unwrap()
+        .fetch(canvas)
+        .await
+        .unwrap()
+        .inner()
+}
+
+#[tokio::test]
+async fn revalidate_with_etag() {
+    let server = FakeCanvas::start();
+    serve_course(&server);
+    let canvas = server.builder().cache(Cache::memory()).build().unwrap();
+
+    assert_eq!(get_course(&canvas).await.id, 1);
+    let course = get_course(&canvas).await;
+    assert_eq!(course.name.as_deref(), Some("Introduction to Rust"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].header("If-None-Match"), None);
+    assert_eq!(requests[1].header("If-None-Match"), Some("\"v1\""));
+}
+
+#[tokio::test]
+async fn revalidate_with_last_modified() {
+    let server = FakeCanvas::start();
+    let last_modified = "Wed, 21 Oct 2026 07:28:00 GMT";
+    server
+        .get(
+            "/api/v1/courses/1",
+            server
+                .fixture("course.json")
+                .header("Last-Modified", last_modified),
+        )
+        .get("/api/v1/courses/1", Response::json("").status(304));
+    let canvas = server.builder().cache(Cache::memory()).build().unwrap();
+
+    get_course(&canvas).await;
+    assert_eq!(get_course(&canvas).await.id, 1);
+
+    let requests = server.requests();
+    assert_eq!(requests[1].header("If-Modified-Since"), Some(last_modified));
+}
+
+#[tokio::test]
+async fn fresh_responses_are_not_revalidated() {
+    let server = FakeCanvas::start();
+    server.get("/api/v1/courses/1", server.fixture("course.json"));
+    let canvas = server
+        .builder()
+        .cache(Cache::memory().ttl(Duration::from_secs(60)))
+        .build()
+        .unwrap();
+
+    get_course(&canvas).await;
+    assert_eq!(get_course(&canvas).await.id, 1);
+
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn responses_without_validator_are_not_cached() {
+    let server = FakeCanvas::start();
+    server.get("/api/v1/courses/1", server.fixture("course.json"));
+    let canvas = server.builder().cache(Cache::memory()).build().unwrap();
+
+    get_course(&canvas).await;
+    get_course(&canvas).await;
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].header("If-None-Match"), None);
+}
+
+#[tokio::test]
+async fn cache_is_per_token() {
+    let server = FakeCanvas::start();
+    serve_course(&server);
+    let dir = cache_dir("cache_is_per_token");
+    let cache = || Cache::disk(&dir).ttl(Duration::from_secs(60));
+
+    let canvas = server.builder().cache(cache()).build().unwrap();
+    get_course(&canvas).await;
+    get_course(&canvas).await;
+    assert_eq!(server.requests().len(), 1);
+
+    let canvas = CanvasInformation::builder(&server.url(), "WRONG_TOKEN")
+        .cache(cache())
+        .build()
+        .unwrap();
+    let error = Canvas::get_course(1)
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(error, CanvasError::Unauthorized(_)));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn every_page_is_cached() {
+    let server = FakeCanvas::start();
+    server
+        .get(
+            "/api/v1/courses/1/search_users",
+            server
+                .fixture("users_page_1.json")
+                .header("ETag", "\"page-1\"")
+                .header(
+                    "Link",
+                    &server.link(&[("next", "/api/v1/courses/1/search_users?page=2")]),
+                ),
+        )
+        .get(
+            "/api/v1/courses/1/search_users?page=2",
+            server
+                .fixture("users_page_2.json")
+                .header("ETag", "\"page-2\""),
+        );
+    let canvas = server.builder().cache(Cache::memory()).build().unwrap();
+    let course: Course = serde_json::from_str(r#"{"id": 1}"#).unwrap();
+
+    for _ in 0..2 {
+        let users = course
+            .get_users()
+            .unwrap()
+            .fetch(&canvas)
+            .await
+            .unwrap()
+            .inner();
+        assert_eq!(users.len(), 3);
+    }
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 4);
+    assert_eq!(requests[2].header("If-None-Match"), Some("\"page-1\""));
+    assert_eq!(requests[3].header("If-None-Match"), Some("\"page-2\""));
+}
+
+#[tokio::test]
+async fn disk_cache() {
+    let server = FakeCanvas::start();
+    serve_course(&server);
+    let dir = cache_dir("disk_cache");
+
+    let canvas = server.builder().cache(Cache::disk(&dir)).build().unwrap();
+    get_course(&canvas).await;
+
+    let content = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|file| std::fs::read_to_string(file.unwrap().path()).unwrap())
+        .collect::<String>();
+    assert!(content.contains("Introduction to Rust"));
+    assert!(!content.contains(common::TOKEN));
+
+    // A new cache on the same directory reuses the stored response.
+    let canvas = server.builder().cache(Cache::disk(&dir)).build().unwrap();
+    let course = get_course(&canvas).await;
+    assert_eq!(course.name.as_deref(), Some("Introduction to Rust"));
+    assert_eq!(server.requests()[1].header("If-None-Match"), Some("\"v1\""));
+
+    Cache::disk(&dir).clear().unwrap();
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+/// Serve courses 1 and 2, each with its own ETag.
+fn serve_courses(server: &FakeCanvas) {
+    for id in [1, 2] {
+        server.get(
+            &format!("/api/v1/courses/{id}"),
+            server
+                .fixture("course.json")
+                .header("ETag", &format!("\"course-{id}\"")),
+        );
+    }
+}
+
+#[tokio::test]
+async fn least_recently_used_responses_are_evicted() {
+    let server = FakeCanvas::start();
+    serve_courses(&server);
+    let canvas = server
+        .builder()
+        .cache(Cache::memory().max_bytes(300))
+        .build()
+        .unwrap();
+
+    for id in [1, 2, 2, 1] {
+        Canvas::get_course(id)
+            .unwrap()
+            .fetch(&canvas)
+            .await
+            .unwrap();
+    }
+
+    let revalidated: Vec<_> = server
+        .requests()
+        .iter()
+        .map(|request| request.header("If-None-Match").map(str::to_string))
+        .collect();
+    assert_eq!(
+        revalidated,
+        [None, None, Some("\"course-2\"".to_string()), None]
+    );
+}
+
+#[tokio::test]
+async fn responses_larger_than_the_cache_are_not_cached() {
+    let server = FakeCanvas::start();
+    serve_courses(&server);
+    let canvas = server
+        .builder()
+        .cache(Cache::memory().max_bytes(100))
+        .build()
+        .unwrap();
+
+    get_course(&canvas).await;
+    get_course(&canvas).await;
+
+    assert_eq!(server.requests()[1].header("If-None-Match"), None);
+}
+
+#[tokio::test]
+async fn disk_cache_removes_the_oldest_files() {
+    let server = FakeCanvas::start();
+    serve_courses(&server);
+    let dir = cache_dir("disk_cache_removes_the_oldest_files");
+
+    let canvas = server.builder().cache(Cache::disk(&dir)).build().unwrap();
+    get_course(&canvas).await;
+    let size = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|file| file.unwrap().metadata().unwrap().len())
+        .sum::<u64>();
+
+    let canvas = server
+        .builder()
+        .cache(Cache::disk(&dir).max_bytes(size + size / 2))
+        .build()
+        .unwrap();
+    Canvas::get_course(2).unwrap().fetch(&canvas).await.unwrap();
+
+    let files: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|file| std::fs::read_to_string(file.unwrap().path()).unwrap())
+        .collect();
+    assert_eq!(files.len(), 1);
+    assert!(files[0].contains("courses/2"));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn downloads_are_not_cached() {
+    let server = FakeCanvas::start();
+    server.get(
+        "/files/31/download",
+        Response::bytes(b"Week 1: ownership").header("ETag", "\"file-31\""),
+    );
+    let canvas = server
+        .builder()
+        .cache(Cache::memory().ttl(Duration::from_secs(60)))
+        .build()
+        .unwrap();
+    let dir = cache_dir("downloads_are_not_cached");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let file: File = serde_json::from_str(&format!(
+        r#"{{"id": 31, "filename": "syllabus.txt", "url": "{}/files/31/download"}}"#,
+        server.url()
+    ))
+    .unwrap();
+    for _ in 0..2 {
+        file.download(&canvas, dir.to_str().unwrap()).await.unwrap();
+    }
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].header("If-None-Match"), None);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn non_json_responses_are_not_cached() {
+    let server = FakeCanvas::start();
+    server.get(
+        "/api/v1/courses/1",
+        Response::bytes(br#"{"id": 1, "name": "Introduction to Rust"}"#).header("ETag", "\"v1\""),
+    );
+    let canvas = server.builder().cache(Cache::memory()).build().unwrap();
+
+    get_course(&canvas).await;
+    get_course(&canvas).await;
+
+    assert_eq!(server.requests()[1].header("If-None-Match"), None);
+}
+
+/// A transport that answers every request with course 1 and an ETag.
+struct CourseTransport;
+
+impl Transport for CourseTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, CanvasError>> {
+        Box::pin(async move {
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert("Content-Type", "application/json".parse().unwrap());
+            headers.insert("ETag", "\"v1\"".parse().unwrap());
+            Ok(HttpResponse {
+                status: reqwest::StatusCode::OK,
+                headers,
+                url: request.url,
+                body: r#"{"id": 1}"#.into(),
+            })
+        })
+    }
+}
+
+#[tokio::test]
+async fn disk_cache_keys_are_stable() {
+    let dir = cache_dir("disk_cache_keys_are_stable");
+    let canvas = CanvasInformation::builder("https://canvas.test", "MY_TOKEN")
+        .transport(CourseTransport)
+        .cache(Cache::disk(&dir))
+        .build()
+        .unwrap();
+    get_course(&canvas).await;
+
+    // The file name and the key must not change between Rust releases, as the cached responses
+    // would be orphaned.
+    let content = std::fs::read_to_string(dir.join("d1f4335ad305d35a.json")).unwrap();
+    assert!(content.contains(r#""key":"95a82786ec7c3661 https://canvas.test/api/v1/courses/1""#));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,283 @@
+extern crate canvasapi;
+use canvasapi::endpoint::canvas_endpoint;
+use canvasapi::prelude::*;
+use serde::Deserialize;
+
+mod common;
+use common::{FakeCanvas, Response};
+
+#[derive(Debug, Deserialize)]
+struct UserName {
+    id: usize,
+    name: String,
+}
+
+/// An endpoint that is declared outside of the crate.
+#[canvas_endpoint(
+    GET,
+    "courses/{course_id}/search_users",
+    paged,
+    output = UserName,
+    parameter = EnrollmentType::Student
+)]
+struct SearchStudents {
+    course_id: usize,
+    search_term: Option<String>,
+    include: Vec<&'static str>,
+    #[query(name = "sort")]
+    sort_on: &'static str,
+}
+
+#[canvas_endpoint(GET, "users/{user_id}/page_views", output = usize, key = "count")]
+struct PageViewCount {
+    user_id: usize,
+}
+
+#[canvas_endpoint(PUT, "courses/{course_id}/users/{user_id}", output = UserName)]
+struct SetNickname {
+    course_id: usize,
+    user_id: usize,
+    #[body]
+    nickname: String,
+    #[body]
+    locale: Option<String>,
+}
+
+fn serve_students(server: &FakeCanvas) {
+    server
+        .get(
+            "/api/v1/courses/1/search_users",
+            server.fixture("users_page_1.json").header(
+                "Link",
+                &server.link(&[("next", "/api/v1/courses/1/search_users?page=2")]),
+            ),
+        )
+        .get(
+            "/api/v1/courses/1/search_users?page=2",
+            server.fixture("users_page_2.json"),
+        );
+}
+
+#[tokio::test]
+async fn path_and_query_parameters() {
+    let server = FakeCanvas::start();
+    serve_students(&server);
+    let canvas = server.canvas();
+
+    let endpoint = SearchStudents {
+        course_id: 1,
+        search_term: None,
+        include: vec!["email", "avatar_url"],
+        sort_on: "username",
+    };
+    assert_eq!(endpoint.path(), "courses/1/search_users");
+    assert_eq!(
+        endpoint.template(),
+        Some("courses/{course_id}/search_users")
+    );
+
+    let users = endpoint.fetch(&canvas).await.unwrap().inner();
+    let ids: Vec<_> = users.iter().map(|user| user.id).collect();
+    assert_eq!(ids, [101, 102, 103]);
+
+    let query: Vec<_> = server.requests()[0]
+        .query
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect();
+    assert_eq!(
+        query,
+        [
+            "enrollment_type[]=student",
+            "include[]=email",
+            "include[]=avatar_url",
+            "sort=username"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn optional_parameters_are_sent_when_present() {
+    let server = FakeCanvas::start();
+    serve_students(&server);
+    let canvas = server.canvas();
+
+    SearchStudents {
+        course_id: 1,
+        search_term: Some("Ada".into()),
+        include: vec![],
+        sort_on: "email",
+    }
+    .query()
+    .per_page(50)
+    .fetch(&canvas)
+    .await
+    .unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.query_values("search_term"), ["Ada"]);
+    assert_eq!(request.query_values("per_page"), ["50"]);
+    assert!(request.query_values("include[]").is_empty());
+}
+
+#[tokio::test]
+async fn value_of_map() {
+    let server = FakeCanvas::start();
+    server.get(
+        "/api/v1/users/5/page_views",
+        Response::json(r#"{"count": "12"}"#),
+    );
+    let canvas = server.canvas();
+
+    let count = PageViewCount { user_id: 5 }
+        .fetch(&canvas)
+        .await
+        .unwrap()
+        .inner();
+    assert_eq!(count, 12);
+}
+
+#[tokio::test]
+async fn method_of_endpoint() {
+    let server = FakeCanvas::start();
+    server.on(
+        "PUT",
+        "/api/v1/courses/1/users/101",
+        Response::json(r#"{"id": 101, "name": "Ada"}"#),
+    );
+    let canvas = server.canvas();
+
+    let user = SetNickname {
+        course_id: 1,
+        user_id: 101,
+        nickname: "Ada".into(),
+        locale: None,
+    }
+    .fetch(&canvas)
+    .await
+    .unwrap()
+    .inner();
+    assert_eq!(user.name, "Ada");
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "PUT");
+    assert!(request.query.is_empty());
+    assert_eq!(request.header("content-type"), Some("application/json"));
+    let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body, serde_json::json!({ "nickname": "Ada" }));
+}
+
+#[tokio::test]
+async fn setters_of_model_requests() {
+    let server = FakeCanvas::start();
+    serve_students(&server);
+    let canvas = server.canvas();
+    let course: Course = serde_json::from_str(r#"{"id": 1}"#).unwrap();
+
+    course
+        .get_users()
+        .unwrap()
+        .enrollment_type(EnrollmentType::Student)
+        .enrollment_type(EnrollmentType::TA)
+        .include(UserInclude::AvatarUrl)
+        .sort(SortOn::Email)
+        .search_term("Ada")
+        .fetch(&canvas)
+        .await
+        .unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.query_values("search_term"), ["Ada"]);
+    assert_eq!(request.query_values("sort"), ["email"]);
+    assert_eq!(request.query_values("enrollment_type[]"), ["student", "ta"]);
+    assert_eq!(request.query_values("include[]"), ["avatar_url"]);
+}
+
+#[test]
+fn setters_of_endpoint() {
+    let endpoint = SearchStudents {
+        course_id: 1,
+        search_term: None,
+        include: vec![],
+        sort_on: "username",
+    }
+    .search_term("Ada")
+    .include("email")
+    .sort_on("email");
+
+    let parameters: Vec<_> = endpoint
+        .parameters()
+        .into_iter()
+        .map(|parameter| format!("{}={}", parameter.name, parameter.value))
+        .collect();
+    assert_eq!(
+        parameters,
+        [
+            "enrollment_type[]=student",
+            "search_term=Ada",
+            "include[]=email",
+            "sort=email"
+        ]
+    );
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn fetch_blocking() {
+    let server = FakeCanvas::start();
+    serve_students(&server);
+    let canvas = server.canvas();
+
+    let users = SearchStudents {
+        course_id: 1,
+        search_term: None,
+        include: vec![],
+        sort_on: "username",
+    }
+    .fetch_blocking(&canvas)
+    .unwrap()
+    .inner();
+    assert_eq!(users.len(), 3);
+}
+
+#[tokio::test]
+async fn values_are_percent_encoded() {
+    let server = FakeCanvas::start();
+    server.get("/api/v1/search/all_courses/", Response::json("[]"));
+    let canvas = server.canvas();
+
+    Canvas::search_course("Data & AI".to_string(), true, false)
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.raw_query, "search=Data+%26+AI&public_only=true");
+    assert_eq!(request.query_values("search"), ["Data & AI"]);
+}
+
+#[tokio::test]
+async fn include_of_single_object() {
+    let server = FakeCanvas::start();
+    server.get("/api/v1/courses/1", server.fixture("course.json"));
+    let canvas = server.canvas();
+
+    Canvas::get_course(1)
+        .unwrap()
+        .include(CourseInclude::Term)
+        .include(CourseInclude::TotalStudents)
+        .fetch(&canvas)
+        .await
+        .unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(
+        request.raw_query,
+        "include%5B%5D=term&include%5B%5D=total_students"
+    );
+    assert_eq!(
+        request.query_values("include[]"),
+        ["term", "total_students"]
+    );
+}
@@ -0,0 +1,55 @@
+extern crate canvasapi;
+use canvasapi::prelude::*;
+use std::time::Duration;
+
+#[test]
+fn builder_accepts_https_url() {
+    let canvas = CanvasInformation::builder("https://canvas.test.be/", "MY_TOKEN")
+        .timeout(Duration::from_secs(30))
+        .connect_timeout(Duration::from_secs(5))
+        .user_agent("canvasapi-tests")
+        .build()
+        .unwrap();
+
+    assert_eq!(canvas.base_url().as_str(), "https://canvas.test.be/");
+}
+
+#[test]
+fn builder_rejects_invalid_url() {
+    assert!(CanvasInformation::builder("canvas.test.be", "MY_TOKEN")
+        .build()
+        .is_err());
+    assert!(
+        CanvasInformation::builder("ftp://canvas.test.be", "MY_TOKEN")
+            .build()
+            .is_err()
+    );
+}
+
+#[test]
+fn debug_does_not_show_token() {
+    let canvas = CanvasInformation::new("https://canvas.test.be", "MY_TOKEN");
+    let clone = canvas.clone();
+
+    assert!(!format!("{:?}", clone).contains("MY_TOKEN"));
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn blocking_and_async_requests_in_one_build() {
+    let canvas = CanvasInformation::new("http://127.0.0.1:1", "MY_TOKEN");
+
+    let error = Canvas::get_todo_items()
+        .unwrap()
+        .fetch_blocking(&canvas)
+        .err()
+        .unwrap();
+    assert!(matches!(error, CanvasError::Http(_)));
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let error = runtime
+        .block_on(Canvas::get_todo_items().unwrap().fetch(&canvas))
+        .err()
+        .unwrap();
+    assert!(matches!(error, CanvasError::Http(_)));
+}
@@ -0,0 +1,177 @@
+extern crate canvasapi;
+use canvasapi::cassette::{Cassette, Player, Recorder, Scrubber, UnmatchedRequest};
+use canvasapi::prelude::*;
+
+mod common;
+use common::FakeCanvas;
+
+fn cassette_path(test: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("canvasapi-{}-{test}.json", std::process::id()))
+}
+
+/// Record the students of course 1, served on two pages.
+async fn record_students(path: &std::path::Path) {
+    let server = FakeCanvas::start();
+    server
+        .get(
+            "/api/v1/courses/1/search_users",
+            server.fixture("users_page_1.json").header(
+                "Link",
+                &server.link(&[("next", "/api/v1/courses/1/search_users?page=2")]),
+            ),
+        )
+        .get(
+            "/api/v1/courses/1/search_users?page=2",
+            server.fixture("users_page_2.json"),
+        );
+
+    let canvas = server
+        .builder()
+        .transport(
+            Recorder::new(path)
+                .scrubber(Scrubber::default().replace(server.url(), "https://canvas.test")),
+        )
+        .build()
+        .unwrap();
+
+    let course: Course = serde_json::from_str(r#"{"id": 1}"#).unwrap();
+    let users = course
+        .get_users()
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .unwrap()
+        .inner();
+    assert_eq!(users.len(), 3);
+}
+
+#[tokio::test]
+async fn record_scrubs_token_and_personal_information() {
+    let path = cassette_path("record");
+    record_students(&path).await;
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(!content.contains(common::TOKEN));
+    assert!(!content.contains("Lovelace"));
+    assert!(!content.contains("127.0.0.1"));
+    assert!(content.contains("https://canvas.test/api/v1/courses/1/search_users?page=2"));
+
+    let cassette = Cassette::open(&path).unwrap();
+    assert_eq!(cassette.interactions.len(), 2);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn replay_without_network() {
+    let path = cassette_path("replay");
+    record_students(&path).await;
+
+    let canvas = CanvasInformation::builder("https://canvas.test", "OTHER_TOKEN")
+        .transport(Player::open(&path).unwrap())
+        .build()
+        .unwrap();
+
+    let course: Course = serde_json::from_str(r#"{"id": 1}"#).unwrap();
+    let users = course
+        .get_users()
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .unwrap()
+        .inner();
+
+    let ids: Vec<_> = users.iter().map(|user| user.id.clone()).collect();
+    assert_eq!(ids, [101, 102, 103]);
+    assert_eq!(users[0].name.as_deref(), Some("REDACTED"));
+
+    let error = Canvas::get_course(2)
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .err()
+        .unwrap();
+    match error {
+        CanvasError::Transport(error) => {
+            let error = error.downcast_ref::<UnmatchedRequest>().unwrap();
+            assert_eq!(error.url, "https://canvas.test/api/v1/courses/2");
+        }
+        error => panic!("Unexpected error: {error}"),
+    }
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn cassette_is_written_on_save() {
+    let path = cassette_path("save");
+    let _ = std::fs::remove_file(&path);
+    let server = FakeCanvas::start();
+    server.get("/api/v1/courses/1", server.fixture("course.json"));
+
+    let recorder = Recorder::new(&path);
+    let canvas = server
+        .builder()
+        .transport(recorder.clone())
+        .build()
+        .unwrap();
+    Canvas::get_course(1).unwrap().fetch(&canvas).await.unwrap();
+    assert!(!path.exists());
+
+    recorder.save().unwrap();
+    assert_eq!(Cassette::open(&path).unwrap().interactions.len(), 1);
+
+    // The interactions after the last save are written when the recorder is dropped.
+    Canvas::get_course(1).unwrap().fetch(&canvas).await.unwrap();
+    drop(canvas);
+    assert_eq!(Cassette::open(&path).unwrap().interactions.len(), 1);
+    drop(recorder);
+    assert_eq!(Cassette::open(&path).unwrap().interactions.len(), 2);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn record_scrubs_sis_ids() {
+    let path = cassette_path("sis_ids");
+    let server = FakeCanvas::start();
+    server.get(
+        "/api/v1/courses/sis_course_id:2024-CS101",
+        server.fixture("course.json"),
+    );
+
+    let canvas = server
+        .builder()
+        .masquerade(UserId::sis("s123456"))
+        .transport(Recorder::new(&path))
+        .build()
+        .unwrap();
+    Canvas::get_course(CourseId::sis("2024-CS101"))
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .unwrap();
+    drop(canvas);
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(!content.contains("2024-CS101"));
+    assert!(!content.contains("s123456"));
+    assert!(content
+        .contains("/api/v1/courses/sis_course_id:REDACTED?as_user_id=sis_user_id%3AREDACTED"));
+
+    // The requests are scrubbed in the same way when they are replayed.
+    let canvas = CanvasInformation::builder(&server.url(), "OTHER_TOKEN")
+        .masquerade(UserId::sis("s123456"))
+        .transport(Player::open(&path).unwrap())
+        .build()
+        .unwrap();
+    let course = Canvas::get_course(CourseId::sis("2024-CS101"))
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .unwrap()
+        .inner();
+    assert_eq!(course.id, 1);
+
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,349 @@
+//! An in-process fake Canvas server for the integration tests.
+//!
+//! The server listens on a random local port and answers the requests with the responses that
+//! are registered for the method, path and query of the request.
+//! The fixtures in `tests/fixtures` are served as response bodies, with `{base_url}` replaced by
+//! the url of the server, such that links in the fixtures point to the fake server.
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use canvasapi::prelude::*;
+
+/// The access token that the fake server accepts by default.
+pub const TOKEN: &str = "FAKE_TOKEN";
+
+/// A fake Canvas server.
+pub struct FakeCanvas {
+    address: SocketAddr,
+    state: Arc<Mutex<State>>,
+    stopped: Arc<AtomicBool>,
+}
+
+struct State {
+    routes: Vec<Route>,
+    requests: Vec<RecordedRequest>,
+    /// The access tokens that are accepted.
+    tokens: Vec<String>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            routes: vec![],
+            requests: vec![],
+            tokens: vec![TOKEN.into()],
+        }
+    }
+}
+
+struct Route {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    /// The responses are served in order, the last one is repeated.
+    responses: VecDeque<Response>,
+}
+
+impl Route {
+    fn matches(&self, request: &RecordedRequest) -> bool {
+        self.method == request.method
+            && self.path == request.path
+            && self.query.iter().all(|pair| request.query.contains(pair))
+    }
+
+    fn next_response(&mut self) -> Response {
+        if self.responses.len() > 1 {
+            self.responses.pop_front().unwrap()
+        } else {
+            self.responses[0].clone()
+        }
+    }
+}
+
+/// A response of the fake server.
+#[derive(Debug, Clone)]
+pub struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    /// A `200 OK` response with a JSON body.
+    pub fn json(body: &str) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".into(), "application/json".into())],
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    /// A `200 OK` response with a binary body.
+    pub fn bytes(body: &[u8]) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".into(), "application/octet-stream".into())],
+            body: body.to_vec(),
+        }
+    }
+
+    /// A Canvas error response, with the message in the `errors` array.
+    pub fn error(status: u16, message: &str) -> Self {
+        Self::json(&serde_json::json!({ "errors": [{ "message": message }] }).to_string())
+            .status(status)
+    }
+
+    pub fn status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// A request that was received by the fake server.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    /// The percent-decoded query parameters.
+    pub query: Vec<(String, String)>,
+    /// The query as it was sent, without the `?`.
+    pub raw_query: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    /// The value of a header, ignoring the case of the name.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The values of a query parameter.
+    pub fn query_values(&self, name: &str) -> Vec<&str> {
+        self.query
+            .iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+}
+
+impl FakeCanvas {
+    /// Start a fake server on a random local port.
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(State::default()));
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let server_state = state.clone();
+        let server_stopped = stopped.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if server_stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                if let Ok(stream) = stream {
+                    let state = server_state.clone();
+                    std::thread::spawn(move || handle_connection(stream, &state));
+                }
+            }
+        });
+
+        Self {
+            address,
+            state,
+            stopped,
+        }
+    }
+
+    /// The url of the server.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// The Canvas information for making requests to the server.
+    pub fn canvas(&self) -> CanvasInformation {
+        CanvasInformation::new(&self.url(), TOKEN)
+    }
+
+    /// A builder for the Canvas information, for example to configure a throttle.
+    pub fn builder(&self) -> CanvasInformationBuilder {
+        CanvasInformation::builder(&self.url(), TOKEN)
+    }
+
+    /// Register a response for the requests with the given method, path and query parameters.
+    ///
+    /// The query of `target` must be a subset of the query of the request. When more targets
+    /// match a request, the one with the most query parameters is used.
+    /// When more responses are registered for the same target, they are served in order, and the
+    /// last one is repeated.
+    pub fn on(&self, method: &str, target: &str, response: Response) -> &Self {
+        let url = url::Url::parse(&format!("{}{target}", self.url())).unwrap();
+        let path = url.path().to_string();
+        let query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+
+        let mut state = self.state.lock().unwrap();
+        match state
+            .routes
+            .iter_mut()
+            .find(|route| route.method == method && route.path == path && route.query == query)
+        {
+            Some(route) => route.responses.push_back(response),
+            None => state.routes.push(Route {
+                method: method.into(),
+                path,
+                query,
+                responses: VecDeque::from([response]),
+            }),
+        }
+        drop(state);
+
+        self
+    }
+
+    /// Register a response for `GET` requests.
+    pub fn get(&self, target: &str, response: Response) -> &Self {
+        self.on("GET", target, response)
+    }
+
+    /// A `200 OK` response with the content of a fixture as body.
+    pub fn fixture(&self, name: &str) -> Response {
+        let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+        let body = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("Failed to read fixture `{path}`: {e}"));
+
+        Response::json(&body.replace("{base_url}", &self.url()))
+    }
+
+    /// The value of a `Link` header, with the targets relative to the server.
+    pub fn link(&self, links: &[(&str, &str)]) -> String {
+        links
+            .iter()
+            .map(|(rel, target)| format!("<{}{target}>; rel=\"{rel}\"", self.url()))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Also accept this access token.
+    pub fn accept_token(&self, token: &str) -> &Self {
+        self.state.lock().unwrap().tokens.push(token.into());
+        self
+    }
+
+    /// The requests that were received, in order.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for FakeCanvas {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // Wake up the listener, such that it notices that it is stopped.
+        let _ = TcpStream::connect(self.address);
+    }
+}
+
+fn handle_connection(stream: TcpStream, state: &Mutex<State>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+        return;
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default().to_string();
+
+    let mut headers = vec![];
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let length = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).unwrap();
+
+    let url = url::Url::parse(&format!("http://fake{target}")).unwrap();
+    let request = RecordedRequest {
+        method,
+        path: url.path().to_string(),
+        query: url.query_pairs().into_owned().collect(),
+        raw_query: url.query().unwrap_or_default().to_string(),
+        headers,
+        body,
+    };
+
+    let response = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(request.clone());
+
+        // Like Canvas, the token endpoint is used without an access token.
+        let authorized = (request.method == "POST" && request.path == "/login/oauth2/token")
+            || state
+                .tokens
+                .iter()
+                .any(|token| request.header("Authorization") == Some(&format!("Bearer {token}")));
+
+        if !authorized {
+            Response::error(401, "Invalid access token.")
+                .header("WWW-Authenticate", "Bearer realm=\"canvas-lms\"")
+        } else {
+            // The route with the most specific query wins.
+            match state
+                .routes
+                .iter_mut()
+                .filter(|route| route.matches(&request))
+                .max_by_key(|route| route.query.len())
+            {
+                Some(route) => route.next_response(),
+                None => Response::error(404, "The specified resource does not exist."),
+            }
+        }
+    };
+
+    write_response(stream, &response);
+}
+
+fn write_response(mut stream: TcpStream, response: &Response) {
+    let mut head = format!("HTTP/1.1 {} Fake\r\n", response.status);
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        response.body.len()
+    ));
+
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(&response.body);
+    let _ = stream.flush();
+}
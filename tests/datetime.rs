@@ -0,0 +1,96 @@
+#![cfg(feature = "chrono")]
+
+extern crate canvasapi;
+use canvasapi::prelude::*;
+use chrono::{DateTime, Duration, Utc};
+
+mod common;
+use common::{FakeCanvas, Response};
+
+fn at(timestamp: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(timestamp).unwrap().into()
+}
+
+#[test]
+fn timestamps_are_parsed() {
+    let submission: Submission = serde_json::from_str(
+        r#"{"submitted_at": "2024-05-01T23:59:59+02:00", "graded_at": null, "late": false}"#,
+    )
+    .unwrap();
+    assert_eq!(submission.submitted_at, Some(at("2024-05-01T21:59:59Z")));
+    assert_eq!(submission.graded_at, None);
+}
+
+#[test]
+fn due_dates_of_assignments() {
+    let now = Utc::now();
+    let mut assignment: Assignment = serde_json::from_str("{}").unwrap();
+    assert!(!assignment.is_overdue());
+    assert_eq!(assignment.time_until_due(), None);
+
+    assignment.due_at = Some(now - Duration::days(1));
+    assert!(assignment.is_overdue());
+    assert!(assignment.time_until_due().unwrap() < Duration::zero());
+
+    assignment.due_at = Some(now + Duration::days(1));
+    assert!(!assignment.is_overdue());
+    let until_due = assignment.time_until_due().unwrap();
+    assert!(until_due > Duration::hours(23) && until_due <= Duration::days(1));
+}
+
+#[test]
+fn locked_quizzes() {
+    let quiz = Quiz {
+        unlock_at: Some(at("2024-05-01T08:00:00Z")),
+        lock_at: Some(at("2024-05-01T10:00:00Z")),
+        ..Default::default()
+    };
+    assert!(quiz.is_locked_at(at("2024-05-01T07:59:59Z")));
+    assert!(!quiz.is_locked_at(at("2024-05-01T08:00:00Z")));
+    assert!(!quiz.is_locked_at(at("2024-05-01T09:59:59Z")));
+    assert!(quiz.is_locked_at(at("2024-05-01T10:00:00Z")));
+
+    assert!(!Quiz::default().is_locked_at(Utc::now()));
+}
+
+#[tokio::test]
+async fn timestamps_of_fetched_assignments() {
+    let server = FakeCanvas::start();
+    server.get(
+        "/api/v1/courses/1/assignments",
+        server.fixture("assignments.json"),
+    );
+    let course: Course = serde_json::from_str(r#"{"id": 1}"#).unwrap();
+
+    let assignments = course
+        .get_assignments()
+        .unwrap()
+        .fetch(&server.canvas())
+        .await
+        .unwrap()
+        .inner();
+    assert_eq!(assignments[0].due_at, Some(at("2022-10-01T21:59:59Z")));
+    assert!(assignments[0].is_overdue());
+    assert_eq!(assignments[1].due_at, None);
+}
+
+#[tokio::test]
+async fn malformed_timestamp_is_an_error() {
+    let server = FakeCanvas::start();
+    server.get(
+        "/api/v1/courses/1/assignments",
+        Response::json(r#"[{"id": 11}, {"id": 12, "due_at": "May 1st"}]"#),
+    );
+    let course: Course = serde_json::from_str(r#"{"id": 1}"#).unwrap();
+
+    let result = course
+        .get_assignments()
+        .unwrap()
+        .fetch(&server.canvas())
+        .await;
+    match result {
+        Err(CanvasError::Deserialize { path, .. }) => assert_eq!(path, "[1].due_at"),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(_) => panic!("The malformed `due_at` is accepted"),
+    }
+}
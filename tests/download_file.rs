@@ -0,0 +1,116 @@
+extern crate canvasapi;
+use canvasapi::prelude::*;
+
+mod common;
+use common::{FakeCanvas, Response};
+
+const CONTENT: &[u8] = b"Week 1: ownership\nWeek 2";
+
+/// A new, empty directory for the downloads of a test.
+fn download_dir(test: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("canvasapi-{}-{test}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn serve_files(server: &FakeCanvas) {
+    server
+        .get("/api/v1/courses/1", server.fixture("course.json"))
+        .get("/api/v1/courses/1/files", server.fixture("files.json"))
+        .get(
+            "/files/31/download?download_frd=1",
+            Response::bytes(CONTENT),
+        );
+}
+
+#[tokio::test]
+async fn download_file() {
+    let server = FakeCanvas::start();
+    serve_files(&server);
+    let canvas = server.canvas();
+    let dir = download_dir("download_file");
+
+    let files = Canvas::get_course(1)
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .unwrap()
+        .inner()
+        .get_files()
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .unwrap()
+        .inner();
+
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].content_type.as_deref(), Some("text/plain"));
+    assert_eq!(files[0].size, Some(CONTENT.len()));
+
+    files[0]
+        .download(&canvas, dir.to_str().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(dir.join("syllabus.txt")).unwrap(), CONTENT);
+    let request = server.requests().pop().unwrap();
+    assert_eq!(
+        request.header("Authorization"),
+        Some(format!("Bearer {}", common::TOKEN).as_str())
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn download_missing_file() {
+    let server = FakeCanvas::start();
+    let canvas = server.canvas();
+    let dir = download_dir("download_missing_file");
+
+    let file: File = serde_json::from_str(&format!(
+        r#"{{"id": 31, "filename": "syllabus.txt", "url": "{}/files/31/download"}}"#,
+        server.url()
+    ))
+    .unwrap();
+
+    let error = file
+        .download(&canvas, dir.to_str().unwrap())
+        .await
+        .err()
+        .unwrap();
+
+    assert!(matches!(error, CanvasError::NotFound(_)));
+    assert!(!dir.join("syllabus.txt").exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn download_file_blocking() {
+    let server = FakeCanvas::start();
+    serve_files(&server);
+    let canvas = server.canvas();
+    let dir = download_dir("download_file_blocking");
+
+    let files = Canvas::get_course(1)
+        .unwrap()
+        .fetch_blocking(&canvas)
+        .unwrap()
+        .inner()
+        .get_files()
+        .unwrap()
+        .fetch_blocking(&canvas)
+        .unwrap()
+        .inner();
+
+    files[0]
+        .download_blocking(&canvas, dir.to_str().unwrap())
+        .unwrap();
+
+    assert_eq!(std::fs::read(dir.join("syllabus.txt")).unwrap(), CONTENT);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,165 @@
+extern crate canvasapi;
+use canvasapi::prelude::*;
+use serde::Deserialize;
+
+mod common;
+use common::{FakeCanvas, Response};
+
+/// An endpoint that is defined outside of the crate.
+struct CourseUsers {
+    course_id: usize,
+    enrollment_type: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserName {
+    id: usize,
+    name: String,
+}
+
+impl Endpoint for CourseUsers {
+    type Output = UserName;
+
+    fn path(&self) -> String {
+        format!("courses/{}/search_users", self.course_id)
+    }
+
+    fn parameters(&self) -> Vec<RequestParameter> {
+        vec![RequestParameter {
+            name: "enrollment_type[]".into(),
+            value: self.enrollment_type.into(),
+        }]
+    }
+}
+
+/// An endpoint with a body.
+struct Rename {
+    user_id: usize,
+    name: &'static str,
+}
+
+impl Endpoint for Rename {
+    type Output = UserName;
+
+    fn method(&self) -> reqwest::Method {
+        reqwest::Method::PUT
+    }
+
+    fn path(&self) -> String {
+        format!("users/{}", self.user_id)
+    }
+
+    fn body(&self) -> Result<Option<serde_json::Value>, CanvasError> {
+        Ok(Some(serde_json::json!({ "user": { "name": self.name } })))
+    }
+}
+
+#[tokio::test]
+async fn paged_query_of_custom_endpoint() {
+    let server = FakeCanvas::start();
+    server
+        .get(
+            "/api/v1/courses/1/search_users?enrollment_type[]=student",
+            server.fixture("users_page_1.json").header(
+                "Link",
+                &server.link(&[(
+                    "next",
+                    "/api/v1/courses/1/search_users?enrollment_type[]=student&page=2",
+                )]),
+            ),
+        )
+        .get(
+            "/api/v1/courses/1/search_users?enrollment_type[]=student&page=2",
+            server.fixture("users_page_2.json"),
+        );
+    let canvas = server.canvas();
+
+    let users = PagedQuery::new(CourseUsers {
+        course_id: 1,
+        enrollment_type: "student",
+    })
+    .per_page(2)
+    .fetch(&canvas)
+    .await
+    .unwrap()
+    .inner();
+
+    let ids: Vec<_> = users.iter().map(|user| user.id).collect();
+    assert_eq!(ids, [101, 102, 103]);
+
+    let first = &server.requests()[0];
+    assert_eq!(
+        first.query,
+        [
+            ("enrollment_type[]".to_string(), "student".to_string()),
+            ("per_page".to_string(), "2".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn query_of_custom_endpoint_with_body() {
+    let server = FakeCanvas::start();
+    server.on(
+        "PUT",
+        "/api/v1/users/101",
+        Response::json(r#"{"id": 101, "name": "Ada King"}"#),
+    );
+    let canvas = server.canvas();
+
+    let user = Query::new(Rename {
+        user_id: 101,
+        name: "Ada King",
+    })
+    .fetch(&canvas)
+    .await
+    .unwrap()
+    .inner();
+    assert_eq!(user.name, "Ada King");
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "PUT");
+    let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["user"]["name"], "Ada King");
+}
+
+#[tokio::test]
+async fn model_requests_are_endpoints() {
+    let server = FakeCanvas::start();
+    server.get("/api/v1/courses/1", server.fixture("course.json"));
+    let canvas = server.canvas();
+
+    let request = Canvas::get_course(1).unwrap();
+    assert_eq!(request.endpoint().path(), "courses/1");
+    assert_eq!(request.endpoint().template(), Some("courses/{course_id}"));
+
+    let course = request.fetch(&canvas).await.unwrap().inner();
+    assert_eq!(course.id, 1);
+}
+
+#[tokio::test]
+async fn parameters_of_raw_endpoint() {
+    let server = FakeCanvas::start();
+    server.get(
+        "/api/v1/courses/1/users",
+        Response::json(r#"[{"id": 101, "name": "Ada"}]"#),
+    );
+
+    let users = Query::new(canvasapi::endpoint::RawEndpoint::<Vec<UserName>>::get(
+        "courses/1/users",
+    ))
+    .parameter(EnrollmentType::Student)
+    .parameter(RequestParameter {
+        name: "include[]".into(),
+        value: "email".into(),
+    })
+    .fetch(&server.canvas())
+    .await
+    .unwrap()
+    .inner();
+    assert_eq!(users[0].name, "Ada");
+
+    let request = &server.requests()[0];
+    assert_eq!(request.query_values("enrollment_type[]"), ["student"]);
+    assert_eq!(request.query_values("include[]"), ["email"]);
+}
@@ -0,0 +1,109 @@
+extern crate canvasapi;
+use canvasapi::prelude::*;
+
+mod common;
+use common::FakeCanvas;
+
+fn serve_assignments(server: &FakeCanvas) {
+    server
+        .get("/api/v1/courses/1", server.fixture("course.json"))
+        .get(
+            "/api/v1/courses/1/assignments",
+            server.fixture("assignments.json"),
+        )
+        .get(
+            "/api/v1/courses/1/assignments/11/submissions",
+            server.fixture("submissions.json"),
+        );
+}
+
+#[tokio::test]
+async fn get_submissions() {
+    let server = FakeCanvas::start();
+    serve_assignments(&server);
+    let canvas = server.canvas();
+
+    let course = Canvas::get_course(1)
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .unwrap()
+        .inner();
+
+    let assignments = course
+        .get_assignments()
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .unwrap()
+        .inner();
+    assert_eq!(assignments.len(), 2);
+    assert_eq!(assignments[0].points_possible, Some(10.0));
+
+    let submissions = assignments[0]
+        .get_submissions()
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .unwrap()
+        .inner();
+
+    let scores: Vec<_> = submissions
+        .iter()
+        .map(|submission| (submission.user_id.clone().unwrap(), submission.score))
+        .collect();
+    assert_eq!(
+        scores,
+        [
+            (UserId::Id(101), Some(9.0)),
+            (UserId::Id(102), Some(7.0)),
+            (UserId::Id(103), None)
+        ]
+    );
+
+    assert!(!submissions[0].late);
+    assert!(submissions[1].late);
+    assert_eq!(submissions[1].seconds_late, Some(41430));
+    assert_eq!(submissions[2].missing, Some(true));
+    assert_eq!(
+        submissions[2].workflow_state.as_deref(),
+        Some("unsubmitted")
+    );
+}
+
+#[tokio::test]
+async fn get_submissions_without_course() {
+    let assignment: Assignment = serde_json::from_str(r#"{"id": 11}"#).unwrap();
+
+    let error = assignment.get_submissions().err().unwrap();
+
+    assert!(matches!(error, CanvasError::MissingField(field) if field == "course_id"));
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn get_submissions_blocking() {
+    let server = FakeCanvas::start();
+    serve_assignments(&server);
+    let canvas = server.canvas();
+
+    let assignments = Canvas::get_course(1)
+        .unwrap()
+        .fetch_blocking(&canvas)
+        .unwrap()
+        .inner()
+        .get_assignments()
+        .unwrap()
+        .fetch_blocking(&canvas)
+        .unwrap()
+        .inner();
+
+    let submissions = assignments[0]
+        .get_submissions()
+        .unwrap()
+        .fetch_blocking(&canvas)
+        .unwrap()
+        .inner();
+
+    assert_eq!(submissions.len(), 3);
+}
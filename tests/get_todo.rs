@@ -1,25 +1,53 @@
 extern crate canvasapi;
 use canvasapi::prelude::*;
 
-#[cfg(not(feature = "blocking"))]
+mod common;
+use common::FakeCanvas;
+
 mod test_async_todo {
     use super::*;
 
     #[tokio::test]
     async fn get_todo() {
-        dotenv::dotenv().ok();
+        let server = FakeCanvas::start();
+        server.get("/api/v1/users/self/todo", server.fixture("todo.json"));
+
+        let todo = Canvas::get_todo_items()
+            .unwrap()
+            .fetch(&server.canvas())
+            .await
+            .unwrap()
+            .inner();
+
+        assert_eq!(todo.len(), 2);
+
+        let assignment = todo[0].assignment.as_ref().unwrap();
+        assert_eq!(assignment.id, Some(AssignmentId(11)));
+        assert_eq!(assignment.name.as_deref(), Some("Ownership and borrowing"));
+        assert_eq!(todo[0].needs_grading_count, Some(3));
+        assert_eq!(todo[0].course_id, Some(CourseId::Id(1)));
 
-        let base_url = std::env::var("CANVAS_BASE_URL").unwrap();
-        let canvas_token = std::env::var("CANVAS_ACCESS_TOKEN").unwrap();
+        let quiz = todo[1].quiz.as_ref().unwrap();
+        assert_eq!(quiz.title.as_deref(), Some("Lifetimes quiz"));
+        assert!(todo[1].assignment.is_none());
+        assert_eq!(
+            todo[1].html_url.as_deref(),
+            Some(format!("{}/courses/1/quizzes/21", server.url()).as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn get_empty_todo() {
+        let server = FakeCanvas::start();
+        server.get("/api/v1/users/self/todo", common::Response::json("[]"));
 
-        let canvas = CanvasInformation::new(&base_url, &canvas_token);
         let todo = Canvas::get_todo_items()
             .unwrap()
-            .fetch(&canvas)
+            .fetch(&server.canvas())
             .await
             .unwrap()
             .inner();
 
-        println!("{:#?}", todo);
+        assert!(todo.is_empty());
     }
 }
@@ -1,6 +1,52 @@
 extern crate canvasapi;
 use canvasapi::prelude::*;
-use dotenv::dotenv;
+use std::time::Duration;
+
+mod common;
+use common::{FakeCanvas, Response};
+
+/// Serve course 1, with its students on two pages.
+fn serve_students(server: &FakeCanvas) {
+    server
+        .get("/api/v1/courses/1", server.fixture("course.json"))
+        .get(
+            "/api/v1/courses/1/search_users",
+            server.fixture("users_page_1.json").header(
+                "Link",
+                &server.link(&[
+                    (
+                        "current",
+                        "/api/v1/courses/1/search_users?enrollment_type[]=student&page=1",
+                    ),
+                    (
+                        "next",
+                        "/api/v1/courses/1/search_users?enrollment_type[]=student&page=2",
+                    ),
+                    (
+                        "last",
+                        "/api/v1/courses/1/search_users?enrollment_type[]=student&page=2",
+                    ),
+                ]),
+            ),
+        )
+        .get(
+            "/api/v1/courses/1/search_users?page=2",
+            server.fixture("users_page_2.json").header(
+                "Link",
+                &server.link(&[(
+                    "current",
+                    "/api/v1/courses/1/search_users?enrollment_type[]=student&page=2",
+                )]),
+            ),
+        );
+}
+
+fn names(users: &[User]) -> Vec<&str> {
+    users
+        .iter()
+        .map(|user| user.name.as_deref().unwrap())
+        .collect()
+}
 
 #[cfg(feature = "blocking")]
 mod tests_blocking {
@@ -8,191 +54,334 @@ mod tests_blocking {
 
     #[test]
     fn get_students() {
-        dotenv().ok();
-
-        let base_url = std::env::var("CANVAS_BASE_URL").unwrap();
-        let canvas_token = std::env::var("CANVAS_ACCESS_TOKEN").unwrap();
-
-        let canvas = CanvasInformation::new(&base_url, &canvas_token);
+        let server = FakeCanvas::start();
+        serve_students(&server);
+        let canvas = server.canvas();
 
-        let course = Canvas::get_course(13369)
+        let course = Canvas::get_course(1)
             .unwrap()
-            .fetch(&canvas)
+            .fetch_blocking(&canvas)
             .unwrap()
             .inner();
 
         let users = course
             .get_users()
             .unwrap()
-            .add_parameter(EnrollmentType::Student)
-            .fetch(&canvas)
+            .enrollment_type(EnrollmentType::Student)
+            .fetch_blocking(&canvas)
+            .unwrap()
+            .inner();
+
+        assert_eq!(
+            names(&users),
+            ["Ada Lovelace", "Alan Turing", "Grace Hopper"]
+        );
+    }
+
+    #[test]
+    fn iterate_students() {
+        let server = FakeCanvas::start();
+        serve_students(&server);
+        let canvas = server.canvas();
+
+        let course = Canvas::get_course(1)
+            .unwrap()
+            .fetch_blocking(&canvas)
             .unwrap()
             .inner();
 
-        println!("{:#?}", users);
+        let ids: Vec<UserId> = course
+            .get_users()
+            .unwrap()
+            .enrollment_type(EnrollmentType::Student)
+            .iter(&canvas)
+            .map(|user| user.unwrap().id)
+            .collect();
+
+        assert_eq!(ids, [101, 102, 103]);
     }
 }
 
-#[cfg(not(feature = "blocking"))]
 mod tests_async {
     use super::*;
 
     #[tokio::test]
     async fn get_students() {
-        dotenv().ok();
-
-        let base_url = std::env::var("CANVAS_BASE_URL").unwrap();
-        let canvas_token = std::env::var("CANVAS_ACCESS_TOKEN").unwrap();
-
-        let canvas = CanvasInformation::new(&base_url, &canvas_token);
+        let server = FakeCanvas::start();
+        serve_students(&server);
+        let canvas = server.canvas();
 
-        let course = Canvas::get_course(13369)
+        let course = Canvas::get_course(1)
             .unwrap()
             .fetch(&canvas)
             .await
             .unwrap()
             .inner();
+        assert_eq!(course.id, 1);
+        assert_eq!(course.name.as_deref(), Some("Introduction to Rust"));
 
-        let users = course
+        let response = course
             .get_users()
             .unwrap()
-            .add_parameter(EnrollmentType::Student)
+            .enrollment_type(EnrollmentType::Student)
             .fetch(&canvas)
             .await
-            .unwrap()
-            .inner();
-
-        println!("{:#?}", users);
+            .unwrap();
+        assert_eq!(response.page_metadata().len(), 2);
+
+        let users = response.inner();
+        assert_eq!(
+            names(&users),
+            ["Ada Lovelace", "Alan Turing", "Grace Hopper"]
+        );
+        assert_eq!(users[0].login_id.as_deref(), Some("ada"));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[1].query_values("enrollment_type[]"), ["student"]);
+        assert_eq!(requests[2].query_values("page"), ["2"]);
+        assert_eq!(requests[2].query_values("enrollment_type[]"), ["student"]);
     }
 
     #[tokio::test]
-    async fn get_courses() {
-        dotenv().ok();
+    async fn get_students_concurrently() {
+        let server = FakeCanvas::start();
+        serve_students(&server);
+        let canvas = server.canvas();
 
-        let base_url = std::env::var("CANVAS_BASE_URL").unwrap();
-        let canvas_token = std::env::var("CANVAS_ACCESS_TOKEN").unwrap();
-
-        let canvas = CanvasInformation::new(&base_url, &canvas_token);
+        let course = Canvas::get_course(1)
+            .unwrap()
+            .fetch(&canvas)
+            .await
+            .unwrap()
+            .inner();
 
-        let _ = Course::courses()
+        let users = course
+            .get_users()
             .unwrap()
+            .enrollment_type(EnrollmentType::Student)
+            .concurrency(4)
             .fetch(&canvas)
             .await
             .unwrap()
             .inner();
+
+        assert_eq!(
+            names(&users),
+            ["Ada Lovelace", "Alan Turing", "Grace Hopper"]
+        );
     }
 
     #[tokio::test]
-    async fn get_assignments() {
-        dotenv().ok();
-
-        let base_url = std::env::var("CANVAS_BASE_URL").unwrap();
-        let canvas_token = std::env::var("CANVAS_ACCESS_TOKEN").unwrap();
+    async fn get_single_page() {
+        let server = FakeCanvas::start();
+        serve_students(&server);
+        let canvas = server.canvas();
 
-        let canvas = CanvasInformation::new(&base_url, &canvas_token);
-
-        let course = Canvas::get_course(13312)
+        let course = Canvas::get_course(1)
             .unwrap()
             .fetch(&canvas)
             .await
             .unwrap()
             .inner();
 
-        let _ = course
-            .get_assignments()
+        let page = course
+            .get_users()
             .unwrap()
-            .fetch(&canvas)
+            .enrollment_type(EnrollmentType::Student)
+            .fetch_page(&canvas, 2)
             .await
-            .unwrap()
-            .inner();
+            .unwrap();
+
+        assert_eq!(names(&page.items), ["Grace Hopper"]);
+        assert_eq!(page.links.current_page(), Some(2));
+        assert_eq!(page.links.next, None);
     }
 
     #[tokio::test]
-    async fn get_files() {
-        dotenv().ok();
-
-        let base_url = std::env::var("CANVAS_BASE_URL").unwrap();
-        let canvas_token = std::env::var("CANVAS_ACCESS_TOKEN").unwrap();
-
-        let canvas = CanvasInformation::new(&base_url, &canvas_token);
+    async fn get_courses() {
+        let server = FakeCanvas::start();
+        server.get("/api/v1/courses", server.fixture("courses.json"));
 
-        let course = Canvas::get_course(13312)
+        let courses = Course::courses()
             .unwrap()
-            .fetch(&canvas)
+            .fetch(&server.canvas())
             .await
             .unwrap()
             .inner();
 
-        let files = course
-            .get_files()
+        let codes: Vec<_> = courses
+            .iter()
+            .map(|course| course.course_code.as_deref().unwrap())
+            .collect();
+        assert_eq!(codes, ["RUST101", "RUST201"]);
+    }
+
+    #[tokio::test]
+    async fn get_conv_unread_count() {
+        let server = FakeCanvas::start();
+        server.get(
+            "/api/v1/conversations/unread_count",
+            Response::json(r#"{"unread_count": "3"}"#),
+        );
+
+        let count = Canvas::conversations_unread_count()
             .unwrap()
-            .fetch(&canvas)
+            .fetch(&server.canvas())
             .await
             .unwrap()
             .inner();
 
-        files.last().unwrap().download(&canvas, ".").await.unwrap();
+        assert_eq!(count, 3);
     }
 
-    // #[tokio::test]
-    // async fn get_assignment_groups() {
-    // dotenv().ok();
-
-    // let base_url = std::env::var("CANVAS_BASE_URL").unwrap();
-    // let canvas_token = std::env::var("CANVAS_ACCESS_TOKEN").unwrap();
-
-    // let canvas = CanvasInformation::new(base_url, canvas_token);
-
-    // let course = Canvas::get_course(13369)
-    // .fetch(&canvas)
-    // .await
-    // .unwrap()
-    // .inner();
-
-    // let assignment_groups = course
-    // .get_assignment_groups()
-    // .fetch(&canvas)
-    // .await
-    // .unwrap()
-    // .inner();
-    // }
-
     #[tokio::test]
-    async fn get_conv_unread_count() {
-        dotenv().ok();
+    async fn course_not_found() {
+        let server = FakeCanvas::start();
 
-        let base_url = std::env::var("CANVAS_BASE_URL").unwrap();
-        let canvas_token = std::env::var("CANVAS_ACCESS_TOKEN").unwrap();
+        let error = Canvas::get_course(404)
+            .unwrap()
+            .fetch(&server.canvas())
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, CanvasError::NotFound(_)));
+        assert_eq!(
+            error.api_error().unwrap().messages,
+            ["The specified resource does not exist."]
+        );
+    }
 
-        let canvas = CanvasInformation::new(&base_url, &canvas_token);
+    #[tokio::test]
+    async fn invalid_token() {
+        let server = FakeCanvas::start();
+        serve_students(&server);
+        let canvas = CanvasInformation::new(&server.url(), "WRONG_TOKEN");
 
-        let count = Canvas::conversations_unread_count()
+        let error = Canvas::get_course(1)
             .unwrap()
             .fetch(&canvas)
             .await
-            .unwrap()
-            .inner();
+            .err()
+            .unwrap();
 
-        println!("{}", count);
+        assert!(matches!(error, CanvasError::Unauthorized(_)));
     }
 
     #[tokio::test]
-    #[cfg(feature = "devel")]
-    async fn get_todos() {
-        dotenv().ok();
+    async fn server_error() {
+        let server = FakeCanvas::start();
+        server.get(
+            "/api/v1/courses/1",
+            Response::error(503, "Service unavailable"),
+        );
+
+        let error = Canvas::get_course(1)
+            .unwrap()
+            .fetch(&server.canvas())
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, CanvasError::Server(_)));
+        assert_eq!(
+            error.status(),
+            Some(reqwest::StatusCode::SERVICE_UNAVAILABLE)
+        );
+    }
 
-        let base_url = std::env::var("CANVAS_BASE_URL").unwrap();
-        let canvas_token = std::env::var("CANVAS_ACCESS_TOKEN").unwrap();
+    #[tokio::test]
+    async fn rate_limited_requests_are_retried() {
+        let server = FakeCanvas::start();
+        server
+            .get(
+                "/api/v1/courses/1",
+                Response::json("403 Forbidden (Rate Limit Exceeded)")
+                    .status(403)
+                    .header("X-Rate-Limit-Remaining", "0.0"),
+            )
+            .get(
+                "/api/v1/courses/1",
+                server
+                    .fixture("course.json")
+                    .header("X-Rate-Limit-Remaining", "650.5")
+                    .header("X-Request-Cost", "1.25")
+                    .header("X-Request-Context-Id", "a1b2c3"),
+            );
+
+        let canvas = server
+            .builder()
+            .throttle(Throttle {
+                retry_wait: Duration::from_millis(1),
+                max_delay: Duration::ZERO,
+                ..Throttle::default()
+            })
+            .build()
+            .unwrap();
+
+        let response = Canvas::get_course(1).unwrap().fetch(&canvas).await.unwrap();
+
+        assert_eq!(server.requests().len(), 2);
+        assert_eq!(canvas.rate_limit_remaining(), Some(650.5));
+        assert_eq!(response.metadata().request_cost(), Some(1.25));
+        assert_eq!(response.metadata().request_context_id(), Some("a1b2c3"));
+        assert_eq!(response.inner().id, 1);
+    }
 
-        let canvas = CanvasInformation::new(&base_url, &canvas_token);
+    /// Send two requests with a throttle, of which the first reports the remaining quota, and
+    /// return the time the second request takes.
+    async fn second_request_time(remaining: &str) -> Duration {
+        let server = FakeCanvas::start();
+        server.get(
+            "/api/v1/courses/1",
+            server
+                .fixture("course.json")
+                .header("X-Rate-Limit-Remaining", remaining),
+        );
+        let canvas = server
+            .builder()
+            .throttle(Throttle {
+                threshold: 200.0,
+                max_delay: Duration::from_millis(800),
+                ..Throttle::default()
+            })
+            .build()
+            .unwrap();
+
+        Canvas::get_course(1).unwrap().fetch(&canvas).await.unwrap();
+        let start = std::time::Instant::now();
+        Canvas::get_course(1).unwrap().fetch(&canvas).await.unwrap();
+        start.elapsed()
+    }
 
-        let todos = Canvas::get_todo_items()
+    #[tokio::test]
+    async fn requests_are_slowed_down_below_the_threshold() {
+        // A quarter of the threshold remains, which delays the request by three quarters of the
+        // maximum delay.
+        assert!(second_request_time("50.0").await >= Duration::from_millis(600));
+        assert!(second_request_time("500.0").await < Duration::from_millis(600));
+    }
+
+    #[tokio::test]
+    async fn rate_limited_without_throttle() {
+        let server = FakeCanvas::start();
+        server.get(
+            "/api/v1/courses/1",
+            Response::json("403 Forbidden (Rate Limit Exceeded)")
+                .status(403)
+                .header("X-Rate-Limit-Remaining", "0.0"),
+        );
+
+        let canvas = server.canvas();
+        let error = Canvas::get_course(1)
             .unwrap()
             .fetch(&canvas)
             .await
-            .unwrap()
-            .inner();
+            .err()
+            .unwrap();
 
-        println!("{:#?}", todos);
+        assert!(matches!(error, CanvasError::RateLimited(_)));
+        assert_eq!(canvas.rate_limit_remaining(), Some(0.0));
     }
 }
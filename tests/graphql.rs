@@ -0,0 +1,180 @@
+extern crate canvasapi;
+use canvasapi::prelude::*;
+use serde::Deserialize;
+
+mod common;
+use common::{FakeCanvas, Response};
+
+const SUBMISSIONS_QUERY: &str = "query($id: ID!, $cursor: String) {
+    course(id: $id) {
+        submissionsConnection(after: $cursor) {
+            nodes { _id score }
+            pageInfo { hasNextPage endCursor }
+        }
+    }
+}";
+
+#[derive(Debug, Deserialize)]
+struct CourseData {
+    course: NamedCourse,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedCourse {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLSubmission {
+    #[serde(rename = "_id")]
+    id: String,
+    score: Option<f64>,
+}
+
+fn body(request: &common::RecordedRequest) -> serde_json::Value {
+    serde_json::from_slice(&request.body).unwrap()
+}
+
+#[tokio::test]
+async fn query_with_variables() {
+    let server = FakeCanvas::start();
+    server.on(
+        "POST",
+        "/api/graphql",
+        Response::json(r#"{"data": {"course": {"name": "Introduction to Rust"}}}"#),
+    );
+
+    let data = GraphQLRequest::<CourseData>::new("query($id: ID!) { course(id: $id) { name } }")
+        .variable("id", "1")
+        .unwrap()
+        .fetch(&server.canvas())
+        .await
+        .unwrap()
+        .inner();
+
+    assert_eq!(data.course.name, "Introduction to Rust");
+
+    let request = &server.requests()[0];
+    assert_eq!(request.header("Content-Type"), Some("application/json"));
+    let body = body(request);
+    assert!(body["query"]
+        .as_str()
+        .unwrap()
+        .starts_with("query($id: ID!)"));
+    assert_eq!(body["variables"]["id"], "1");
+}
+
+#[tokio::test]
+async fn errors_are_returned() {
+    let server = FakeCanvas::start();
+    server.on(
+        "POST",
+        "/api/graphql",
+        Response::json(
+            r#"{
+                "data": null,
+                "errors": [{
+                    "message": "Field 'nme' doesn't exist on type 'Course'",
+                    "locations": [{"line": 1, "column": 30}],
+                    "path": ["query", "course", "nme"]
+                }]
+            }"#,
+        ),
+    );
+
+    let error = GraphQLRequest::<CourseData>::new("query { course(id: 1) { nme } }")
+        .fetch(&server.canvas())
+        .await
+        .err()
+        .unwrap();
+
+    match error {
+        CanvasError::GraphQL(errors) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(
+                errors[0].message,
+                "Field 'nme' doesn't exist on type 'Course'"
+            );
+            assert_eq!(errors[0].locations[0].column, 30);
+            assert_eq!(errors[0].path[1], "course");
+        }
+        error => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[tokio::test]
+async fn follow_page_info() {
+    let server = FakeCanvas::start();
+    server
+        .on(
+            "POST",
+            "/api/graphql",
+            Response::json(
+                r#"{"data": {"course": {"submissionsConnection": {
+                    "nodes": [{"_id": "1", "score": 8.5}, {"_id": "2", "score": null}],
+                    "pageInfo": {"hasNextPage": true, "endCursor": "Mg"}
+                }}}}"#,
+            ),
+        )
+        .on(
+            "POST",
+            "/api/graphql",
+            Response::json(
+                r#"{"data": {"course": {"submissionsConnection": {
+                    "edges": [{"node": {"_id": "3", "score": 10.0}}],
+                    "pageInfo": {"hasNextPage": false, "endCursor": "Mw"}
+                }}}}"#,
+            ),
+        );
+
+    let response = GraphQLPagedRequest::<GraphQLSubmission>::new(
+        SUBMISSIONS_QUERY,
+        "course.submissionsConnection",
+    )
+    .variable("id", "1")
+    .unwrap()
+    .fetch(&server.canvas())
+    .await
+    .unwrap();
+
+    assert_eq!(response.page_metadata().len(), 2);
+    let submissions = response.inner();
+    let ids: Vec<_> = submissions.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, ["1", "2", "3"]);
+    assert_eq!(submissions[0].score, Some(8.5));
+    assert_eq!(submissions[1].score, None);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        body(&requests[0])["variables"]["cursor"],
+        serde_json::Value::Null
+    );
+    assert_eq!(body(&requests[1])["variables"]["cursor"], "Mg");
+    assert_eq!(body(&requests[1])["variables"]["id"], "1");
+}
+
+#[tokio::test]
+async fn missing_connection() {
+    let server = FakeCanvas::start();
+    server.on(
+        "POST",
+        "/api/graphql",
+        Response::json(r#"{"data": {"course": null}}"#),
+    );
+
+    let error = GraphQLPagedRequest::<GraphQLSubmission>::new(
+        SUBMISSIONS_QUERY,
+        "course.submissionsConnection",
+    )
+    .variable("id", "404")
+    .unwrap()
+    .fetch(&server.canvas())
+    .await
+    .err()
+    .unwrap();
+
+    assert!(
+        matches!(error, CanvasError::MissingField(field) if field == "course.submissionsConnection")
+    );
+}
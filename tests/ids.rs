@@ -0,0 +1,96 @@
+extern crate canvasapi;
+use canvasapi::prelude::*;
+
+mod common;
+use common::{FakeCanvas, Response};
+
+#[test]
+fn string_encoded_ids() {
+    let course: Course =
+        serde_json::from_str(r#"{"id": "1", "account_id": "7", "enrollment_term_id": 3}"#).unwrap();
+    assert_eq!(course.id, CourseId::Id(1));
+    assert_eq!(course.account_id, Some(AccountId::Id(7)));
+    assert_eq!(course.enrollment_term_id, Some(EnrollmentTermId(3)));
+
+    let id: AssignmentId = serde_json::from_str(r#""42""#).unwrap();
+    assert_eq!(id, 42);
+    assert!(serde_json::from_str::<AssignmentId>(r#""forty-two""#).is_err());
+    assert!(serde_json::from_str::<AssignmentId>("-1").is_err());
+}
+
+#[test]
+fn ids_serialize_transparently() {
+    assert_eq!(serde_json::to_string(&FileId(5)).unwrap(), "5");
+    assert_eq!(serde_json::to_string(&UserId::Id(101)).unwrap(), "101");
+    assert_eq!(
+        serde_json::to_string(&UserId::sis("s123456")).unwrap(),
+        r#""sis_user_id:s123456""#
+    );
+
+    let id: UserId = serde_json::from_str(r#""sis_user_id:s123456""#).unwrap();
+    assert_eq!(id, UserId::sis("s123456"));
+}
+
+#[test]
+fn sis_ids() {
+    let id: CourseId = "sis_course_id:2024-CS101".parse().unwrap();
+    assert_eq!(id, CourseId::sis("2024-CS101"));
+    assert_eq!(id.get(), None);
+    assert_eq!(id.to_string(), "sis_course_id:2024-CS101");
+
+    let id: CourseId = "1".parse().unwrap();
+    assert_eq!(id.get(), Some(1));
+    assert!("sis_user_id:s123456".parse::<CourseId>().is_err());
+}
+
+#[tokio::test]
+async fn sis_id_in_path() {
+    let server = FakeCanvas::start();
+    server.get(
+        "/api/v1/courses/sis_course_id:2024%2FCS%20101",
+        server.fixture("course.json"),
+    );
+
+    let request = Canvas::get_course(CourseId::sis("2024/CS 101")).unwrap();
+    assert_eq!(
+        request.endpoint().path(),
+        "courses/sis_course_id:2024%2FCS%20101"
+    );
+
+    let course = request.fetch(&server.canvas()).await.unwrap().inner();
+    assert_eq!(course.id, 1);
+}
+
+#[tokio::test]
+async fn sis_id_of_user() {
+    let server = FakeCanvas::start();
+    server.get(
+        "/api/v1/courses/1/assignments/11/submissions/sis_user_id:s123456",
+        Response::json(r#"{"id": "9", "user_id": "101", "late": false}"#),
+    );
+    let assignment: Assignment = serde_json::from_str(r#"{"id": 11, "course_id": 1}"#).unwrap();
+
+    let submission = assignment
+        .get_submission(UserId::sis("s123456"))
+        .unwrap()
+        .fetch(&server.canvas())
+        .await
+        .unwrap()
+        .inner();
+    assert_eq!(submission.id, Some(SubmissionId(9)));
+    assert_eq!(submission.user_id, Some(UserId::Id(101)));
+}
+
+#[test]
+fn typed_context_ids() {
+    let outcome: Outcome =
+        serde_json::from_str(r#"{"id": 1, "context_id": "5", "context_type": "Course"}"#).unwrap();
+    assert_eq!(outcome.course_id(), Some(CourseId::Id(5)));
+    assert_eq!(outcome.account_id(), None);
+
+    let dates: AllDates =
+        serde_json::from_str(r#"{"id": 2, "set_type": "Group", "set_id": 9}"#).unwrap();
+    assert_eq!(dates.id, Some(AssignmentOverrideId(2)));
+    assert_eq!(dates.group_id(), Some(GroupId(9)));
+    assert_eq!(dates.section_id(), None);
+}
@@ -0,0 +1,58 @@
+extern crate canvasapi;
+use canvasapi::pagination::Links;
+
+#[test]
+fn parse_canvas_links() {
+    let links = Links::parse(
+        "<https://canvas.test.be/api/v1/courses?page=2&per_page=10>; rel=\"current\",\
+         <https://canvas.test.be/api/v1/courses?page=3&per_page=10>; rel=\"next\",\
+         <https://canvas.test.be/api/v1/courses?page=1&per_page=10>; rel=\"prev\",\
+         <https://canvas.test.be/api/v1/courses?page=1&per_page=10>; rel=\"first\",\
+         <https://canvas.test.be/api/v1/courses?page=7&per_page=10>; rel=\"last\"",
+    );
+
+    assert_eq!(
+        links.next.as_deref(),
+        Some("https://canvas.test.be/api/v1/courses?page=3&per_page=10")
+    );
+    assert_eq!(
+        links.prev.as_deref(),
+        Some("https://canvas.test.be/api/v1/courses?page=1&per_page=10")
+    );
+    assert_eq!(links.current_page(), Some(2));
+    assert_eq!(links.last_page(), Some(7));
+}
+
+#[test]
+fn parse_links_with_commas_and_spaces() {
+    let links = Links::parse(
+        "<https://canvas.test.be/api/v1/users?search=a,b;c&page=2> ;  rel = \"next\" ,\
+         <https://canvas.test.be/api/v1/users?page=bookmark:WzEsMl0>; title=\"a, b; c\"; rel=first",
+    );
+
+    assert_eq!(
+        links.next.as_deref(),
+        Some("https://canvas.test.be/api/v1/users?search=a,b;c&page=2")
+    );
+    assert_eq!(
+        links.first.as_deref(),
+        Some("https://canvas.test.be/api/v1/users?page=bookmark:WzEsMl0")
+    );
+    assert_eq!(links.last, None);
+}
+
+#[test]
+fn parse_links_with_multiple_relation_types() {
+    let links = Links::parse("<https://canvas.test.be/page=4>; rel=\"NEXT last\"");
+
+    assert_eq!(links.next, links.last);
+    assert!(links.next.is_some());
+}
+
+#[test]
+fn ignore_invalid_links() {
+    let links = Links::parse("garbage, <https://canvas.test.be/a>; rel=\"next\", <unterminated");
+
+    assert_eq!(links.next.as_deref(), Some("https://canvas.test.be/a"));
+    assert_eq!(Links::parse(""), Links::default());
+}
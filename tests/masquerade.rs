@@ -0,0 +1,93 @@
+extern crate canvasapi;
+use canvasapi::prelude::*;
+
+mod common;
+use common::FakeCanvas;
+
+/// Serve the students of course 1 on two pages, with links that do not have the `as_user_id`
+/// parameter.
+fn serve_students(server: &FakeCanvas) {
+    server
+        .get(
+            "/api/v1/courses/1/search_users",
+            server.fixture("users_page_1.json").header(
+                "Link",
+                &server.link(&[
+                    ("next", "/api/v1/courses/1/search_users?page=2"),
+                    ("last", "/api/v1/courses/1/search_users?page=2"),
+                ]),
+            ),
+        )
+        .get(
+            "/api/v1/courses/1/search_users?page=2",
+            server.fixture("users_page_2.json"),
+        );
+}
+
+fn course() -> Course {
+    serde_json::from_str(r#"{"id": 1}"#).unwrap()
+}
+
+fn as_user_ids(server: &FakeCanvas) -> Vec<Vec<String>> {
+    server
+        .requests()
+        .iter()
+        .map(|request| {
+            request
+                .query_values("as_user_id")
+                .into_iter()
+                .map(String::from)
+                .collect()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn masquerade_all_requests() {
+    let server = FakeCanvas::start();
+    serve_students(&server);
+    let canvas = server.builder().masquerade(42).build().unwrap();
+
+    let users = course().get_users().unwrap().fetch(&canvas).await.unwrap();
+
+    assert_eq!(users.inner().len(), 3);
+    assert_eq!(as_user_ids(&server), [["42"], ["42"]]);
+}
+
+#[tokio::test]
+async fn masquerade_single_request_with_sis_id() {
+    let server = FakeCanvas::start();
+    serve_students(&server);
+    let canvas = server.canvas();
+
+    let users = course()
+        .get_users()
+        .unwrap()
+        .masquerade(UserId::sis("s123456"))
+        .concurrency(4)
+        .fetch(&canvas)
+        .await
+        .unwrap();
+
+    assert_eq!(users.inner().len(), 3);
+    assert_eq!(
+        as_user_ids(&server),
+        [["sis_user_id:s123456"], ["sis_user_id:s123456"]]
+    );
+}
+
+#[tokio::test]
+async fn request_masquerade_takes_precedence() {
+    let server = FakeCanvas::start();
+    server.get("/api/v1/courses/1", server.fixture("course.json"));
+    let canvas = server.builder().masquerade(42).build().unwrap();
+
+    Canvas::get_course(1)
+        .unwrap()
+        .masquerade(7)
+        .fetch(&canvas)
+        .await
+        .unwrap();
+
+    assert_eq!(as_user_ids(&server), [["7"]]);
+}
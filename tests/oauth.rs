@@ -0,0 +1,268 @@
+extern crate canvasapi;
+use canvasapi::oauth::{OAuthClient, OAuthToken};
+use canvasapi::prelude::*;
+use std::time::{Duration, SystemTime};
+
+mod common;
+use common::{FakeCanvas, Response};
+
+const REDIRECT_URI: &str = "https://grading.test/callback";
+
+fn client(server: &FakeCanvas) -> OAuthClient {
+    OAuthClient::new(
+        &server.url(),
+        "10000000000001",
+        "CLIENT_SECRET",
+        REDIRECT_URI,
+    )
+    .unwrap()
+}
+
+/// A token of which the access token is expired.
+fn expired_token() -> OAuthToken {
+    OAuthToken {
+        access_token: "EXPIRED_TOKEN".into(),
+        refresh_token: Some("REFRESH_TOKEN".into()),
+        expires_at: Some(SystemTime::now() - Duration::from_secs(60)),
+        user: None,
+    }
+}
+
+/// Serve course 1, and a new access token for the refresh token.
+fn serve_refresh(server: &FakeCanvas) {
+    server
+        .accept_token("NEW_TOKEN")
+        .get("/api/v1/courses/1", server.fixture("course.json"))
+        .on(
+            "POST",
+            "/login/oauth2/token",
+            Response::json(
+                r#"{"access_token": "NEW_TOKEN", "token_type": "Bearer", "expires_in": 3600}"#,
+            ),
+        );
+}
+
+fn body(request: &common::RecordedRequest) -> serde_json::Value {
+    serde_json::from_slice(&request.body).unwrap()
+}
+
+#[test]
+fn invalid_base_url() {
+    for url in [
+        "canvas.test",
+        "ftp://canvas.test",
+        "mailto:admin@canvas.test",
+    ] {
+        let result = OAuthClient::new(url, "10000000000001", "SECRET", REDIRECT_URI);
+        assert!(
+            matches!(result, Err(CanvasError::Config(_))),
+            "`{url}` is accepted"
+        );
+    }
+}
+
+#[test]
+fn authorize_url() {
+    let client = OAuthClient::new(
+        "https://canvas.test",
+        "10000000000001",
+        "SECRET",
+        REDIRECT_URI,
+    )
+    .unwrap()
+    .scope("url:GET|/api/v1/courses")
+    .scope("url:GET|/api/v1/users/:user_id/profile");
+
+    let url = client.authorize_url("STATE");
+    assert_eq!(url.path(), "/login/oauth2/auth");
+
+    let query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    let value = |name: &str| {
+        query
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    };
+    assert_eq!(value("client_id"), Some("10000000000001"));
+    assert_eq!(value("response_type"), Some("code"));
+    assert_eq!(value("redirect_uri"), Some(REDIRECT_URI));
+    assert_eq!(value("state"), Some("STATE"));
+    assert_eq!(
+        value("scope"),
+        Some("url:GET|/api/v1/courses url:GET|/api/v1/users/:user_id/profile")
+    );
+    assert_eq!(value("client_secret"), None);
+}
+
+#[tokio::test]
+async fn exchange_code() {
+    let server = FakeCanvas::start();
+    server.on(
+        "POST",
+        "/login/oauth2/token",
+        Response::json(
+            r#"{
+                "access_token": "ACCESS_TOKEN",
+                "token_type": "Bearer",
+                "user": {"id": 42, "name": "Ada Lovelace", "global_id": "10000000000042"},
+                "refresh_token": "REFRESH_TOKEN",
+                "expires_in": 3600
+            }"#,
+        ),
+    );
+
+    let token = client(&server).exchange_code("CODE").await.unwrap();
+
+    assert_eq!(token.access_token, "ACCESS_TOKEN");
+    assert_eq!(token.refresh_token.as_deref(), Some("REFRESH_TOKEN"));
+    assert_eq!(token.user.as_ref().unwrap().id, 42);
+    assert!(!token.is_expired());
+    assert!(token.expires_at.unwrap() > SystemTime::now() + Duration::from_secs(3500));
+
+    let request = &server.requests()[0];
+    assert_eq!(request.header("Authorization"), None);
+    let body = body(request);
+    assert_eq!(body["grant_type"], "authorization_code");
+    assert_eq!(body["client_secret"], "CLIENT_SECRET");
+    assert_eq!(body["redirect_uri"], REDIRECT_URI);
+    assert_eq!(body["code"], "CODE");
+}
+
+#[tokio::test]
+async fn exchange_invalid_code() {
+    let server = FakeCanvas::start();
+    server.on(
+        "POST",
+        "/login/oauth2/token",
+        Response::json(
+            r#"{"error": "invalid_grant", "error_description": "authorization_code not found"}"#,
+        )
+        .status(400),
+    );
+
+    let error = client(&server).exchange_code("CODE").await.err().unwrap();
+
+    assert_eq!(
+        error.api_error().unwrap().messages,
+        ["authorization_code not found"]
+    );
+}
+
+#[tokio::test]
+async fn refresh_expired_token() {
+    let server = FakeCanvas::start();
+    serve_refresh(&server);
+    let canvas = CanvasInformation::oauth_builder(&server.url(), client(&server), expired_token())
+        .build()
+        .unwrap();
+
+    let course = Canvas::get_course(1)
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .unwrap()
+        .inner();
+    assert_eq!(course.id, 1);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(
+        requests[0].header("Authorization"),
+        Some("Bearer EXPIRED_TOKEN")
+    );
+    assert_eq!(body(&requests[1])["grant_type"], "refresh_token");
+    assert_eq!(body(&requests[1])["refresh_token"], "REFRESH_TOKEN");
+    assert_eq!(
+        requests[2].header("Authorization"),
+        Some("Bearer NEW_TOKEN")
+    );
+
+    let token = canvas.oauth_token().unwrap();
+    assert_eq!(token.access_token, "NEW_TOKEN");
+    assert_eq!(token.refresh_token.as_deref(), Some("REFRESH_TOKEN"));
+    assert!(!token.is_expired());
+}
+
+#[tokio::test]
+async fn refresh_once_for_concurrent_requests() {
+    let server = FakeCanvas::start();
+    serve_refresh(&server);
+    let canvas = server
+        .builder()
+        .oauth(client(&server), expired_token())
+        .build()
+        .unwrap();
+
+    let request = || async { Canvas::get_course(1).unwrap().fetch(&canvas).await.unwrap() };
+    futures::join!(request(), request(), request());
+
+    let refreshes = server
+        .requests()
+        .iter()
+        .filter(|request| request.path == "/login/oauth2/token")
+        .count();
+    assert_eq!(refreshes, 1);
+}
+
+#[tokio::test]
+async fn unauthorized_without_expired_token() {
+    let server = FakeCanvas::start();
+    serve_refresh(&server);
+    server.get(
+        "/api/v1/courses/2",
+        Response::error(401, "user not authorized to perform that action"),
+    );
+    let token = OAuthToken {
+        access_token: common::TOKEN.into(),
+        refresh_token: Some("REFRESH_TOKEN".into()),
+        expires_at: None,
+        user: None,
+    };
+    let canvas = server
+        .builder()
+        .oauth(client(&server), token)
+        .build()
+        .unwrap();
+
+    let error = Canvas::get_course(2)
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .err()
+        .unwrap();
+
+    assert!(matches!(error, CanvasError::Unauthorized(_)));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn personal_token_is_not_refreshed() {
+    let server = FakeCanvas::start();
+    let canvas = CanvasInformation::new(&server.url(), "EXPIRED_TOKEN");
+
+    let error = Canvas::get_course(1)
+        .unwrap()
+        .fetch(&canvas)
+        .await
+        .err()
+        .unwrap();
+
+    assert!(matches!(error, CanvasError::Unauthorized(_)));
+    assert_eq!(server.requests().len(), 1);
+    assert!(canvas.oauth_token().is_none());
+}
+
+#[tokio::test]
+async fn delete_token() {
+    let server = FakeCanvas::start();
+    server.on("DELETE", "/login/oauth2/token", Response::json("{}"));
+
+    server.canvas().delete_token().await.unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "DELETE");
+    assert_eq!(
+        request.header("Authorization"),
+        Some(format!("Bearer {}", common::TOKEN).as_str())
+    );
+}
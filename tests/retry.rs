@@ -0,0 +1,127 @@
+extern crate canvasapi;
+use canvasapi::prelude::*;
+use std::time::{Duration, Instant};
+
+mod common;
+use common::{FakeCanvas, Response};
+
+fn canvas(server: &FakeCanvas) -> CanvasInformation {
+    server
+        .builder()
+        .retry_policy(RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::ZERO,
+            jitter: false,
+            ..RetryPolicy::default()
+        })
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn server_errors_are_retried_up_to_max_attempts() {
+    let server = FakeCanvas::start();
+    server.get(
+        "/api/v1/courses/1",
+        Response::error(503, "service unavailable"),
+    );
+
+    let result = Canvas::get_course(1).unwrap().fetch(&canvas(&server)).await;
+    assert!(matches!(result, Err(CanvasError::Server(_))));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn retried_request_succeeds() {
+    let server = FakeCanvas::start();
+    server
+        .get("/api/v1/courses/1", Response::error(502, "bad gateway"))
+        .get("/api/v1/courses/1", server.fixture("course.json"));
+
+    let course = Canvas::get_course(1)
+        .unwrap()
+        .fetch(&canvas(&server))
+        .await
+        .unwrap()
+        .inner();
+    assert_eq!(course.id, 1);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn post_requests_are_never_retried() {
+    let server = FakeCanvas::start();
+    server.on(
+        "POST",
+        "/api/v1/courses/1/assignments",
+        Response::error(503, "service unavailable"),
+    );
+    let course: Course = serde_json::from_str(r#"{"id": 1}"#).unwrap();
+
+    let result = course
+        .create_assignment()
+        .unwrap()
+        .fetch(&canvas(&server))
+        .await;
+    assert!(matches!(result, Err(CanvasError::Server(_))));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn client_errors_are_not_retried() {
+    let server = FakeCanvas::start();
+    server
+        .get("/api/v1/courses/1", Response::error(404, "not found"))
+        .get(
+            "/api/v1/courses/2",
+            Response::error(400, "invalid parameter"),
+        );
+    let canvas = canvas(&server);
+
+    let result = Canvas::get_course(1).unwrap().fetch(&canvas).await;
+    assert!(matches!(result, Err(CanvasError::NotFound(_))));
+    let result = Canvas::get_course(2).unwrap().fetch(&canvas).await;
+    assert!(matches!(result, Err(CanvasError::Status(_))));
+
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn retry_after_is_honoured() {
+    let server = FakeCanvas::start();
+    server
+        .get(
+            "/api/v1/courses/1",
+            Response::error(503, "service unavailable").header("Retry-After", "1"),
+        )
+        .get("/api/v1/courses/1", server.fixture("course.json"));
+
+    let start = Instant::now();
+    let course = Canvas::get_course(1)
+        .unwrap()
+        .fetch(&canvas(&server))
+        .await
+        .unwrap()
+        .inner();
+    assert_eq!(course.id, 1);
+    assert!(start.elapsed() >= Duration::from_secs(1));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn retries_are_disabled_with_never() {
+    let server = FakeCanvas::start();
+    server.get(
+        "/api/v1/courses/1",
+        Response::error(503, "service unavailable"),
+    );
+    let canvas = server
+        .builder()
+        .retry_policy(RetryPolicy::never())
+        .build()
+        .unwrap();
+
+    let result = Canvas::get_course(1).unwrap().fetch(&canvas).await;
+    assert!(matches!(result, Err(CanvasError::Server(_))));
+    assert_eq!(server.requests().len(), 1);
+}
@@ -0,0 +1,85 @@
+extern crate canvasapi;
+use canvasapi::prelude::*;
+use futures::{StreamExt, TryStreamExt};
+
+mod common;
+use common::{FakeCanvas, Response};
+
+fn course() -> Course {
+    serde_json::from_str(r#"{"id": 1}"#).unwrap()
+}
+
+fn serve_first_page(server: &FakeCanvas) {
+    server.get(
+        "/api/v1/courses/1/search_users",
+        server.fixture("users_page_1.json").header(
+            "Link",
+            &server.link(&[("next", "/api/v1/courses/1/search_users?page=2")]),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn stream_of_all_pages() {
+    let server = FakeCanvas::start();
+    serve_first_page(&server);
+    server.get(
+        "/api/v1/courses/1/search_users?page=2",
+        server.fixture("users_page_2.json"),
+    );
+
+    let users: Vec<User> = course()
+        .get_users()
+        .unwrap()
+        .stream(&server.canvas())
+        .try_collect()
+        .await
+        .unwrap();
+    let ids: Vec<_> = users.into_iter().map(|user| user.id).collect();
+    assert_eq!(ids, [101, 102, 103]);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn stopping_early_skips_the_next_pages() {
+    let server = FakeCanvas::start();
+    serve_first_page(&server);
+    server.get(
+        "/api/v1/courses/1/search_users?page=2",
+        server.fixture("users_page_2.json"),
+    );
+
+    let users: Vec<User> = course()
+        .get_users()
+        .unwrap()
+        .stream(&server.canvas())
+        .take(2)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 2);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].query_values("page").is_empty());
+}
+
+#[tokio::test]
+async fn error_on_a_later_page() {
+    let server = FakeCanvas::start();
+    serve_first_page(&server);
+    server.get(
+        "/api/v1/courses/1/search_users?page=2",
+        Response::error(404, "page not found"),
+    );
+
+    let mut users = std::pin::pin!(course().get_users().unwrap().stream(&server.canvas()));
+
+    assert_eq!(users.next().await.unwrap().unwrap().id, 101);
+    assert_eq!(users.next().await.unwrap().unwrap().id, 102);
+    assert!(matches!(
+        users.next().await,
+        Some(Err(CanvasError::NotFound(_)))
+    ));
+    assert!(users.next().await.is_none());
+}
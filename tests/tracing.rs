@@ -0,0 +1,114 @@
+#![cfg(feature = "tracing")]
+
+extern crate canvasapi;
+use canvasapi::prelude::*;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+mod common;
+use common::FakeCanvas;
+
+/// A subscriber that records the spans and events as text.
+#[derive(Clone, Default)]
+struct Recorder {
+    lines: Arc<Mutex<Vec<String>>>,
+    next_id: Arc<Mutex<u64>>,
+}
+
+impl Recorder {
+    fn lines(&self) -> Vec<String> {
+        self.lines.lock().unwrap().clone()
+    }
+
+    fn push(&self, name: &str, record: impl FnOnce(&mut FieldsText)) {
+        let mut fields = FieldsText(name.to_string());
+        record(&mut fields);
+        self.lines.lock().unwrap().push(fields.0);
+    }
+}
+
+struct FieldsText(String);
+
+impl Visit for FieldsText {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push_str(&format!(" {}={:?}", field.name(), value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push_str(&format!(" {}={}", field.name(), value));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.push(span.metadata().name(), |fields| span.record(fields));
+        let mut id = self.next_id.lock().unwrap();
+        *id += 1;
+        Id::from_u64(*id)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        self.push("event", |fields| event.record(fields));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[tokio::test]
+async fn paged_request_is_traced() {
+    let server = FakeCanvas::start();
+    server
+        .get(
+            "/api/v1/courses/1/search_users",
+            server
+                .fixture("users_page_1.json")
+                .header("X-Rate-Limit-Remaining", "700.0")
+                .header(
+                    "Link",
+                    &server.link(&[("next", "/api/v1/courses/1/search_users?page=2")]),
+                ),
+        )
+        .get(
+            "/api/v1/courses/1/search_users?page=2",
+            server.fixture("users_page_2.json"),
+        );
+    let canvas = server.canvas();
+    let course: Course = serde_json::from_str(r#"{"id": 1}"#).unwrap();
+
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+    course.get_users().unwrap().fetch(&canvas).await.unwrap();
+
+    let lines = recorder.lines();
+    assert!(lines[0].starts_with("fetch method=GET path=courses/{course_id}/search_users"));
+    assert!(lines.contains(&"page page=1".to_string()));
+    assert!(lines.contains(&"page page=2".to_string()));
+
+    let responses: Vec<_> = lines
+        .iter()
+        .filter(|line| line.starts_with("event message=response"))
+        .collect();
+    assert_eq!(responses.len(), 2);
+    assert!(responses[0].contains("path=/api/v1/courses/1/search_users"));
+    assert!(responses[0].contains("status=200"));
+    assert!(responses[0].contains("latency_ms="));
+    assert!(responses[0].contains("rate_limit_remaining=700.0"));
+    assert!(lines
+        .last()
+        .unwrap()
+        .contains("message=fetched all pages pages=2 items=3"));
+
+    assert!(lines.iter().all(|line| !line.contains(common::TOKEN)));
+}
@@ -0,0 +1,96 @@
+extern crate canvasapi;
+use canvasapi::prelude::*;
+use canvasapi::transport::{HttpRequest, HttpResponse, Transport};
+use futures::future::BoxFuture;
+use std::sync::{Arc, Mutex};
+
+/// The status and body of a response, or the error of a failed request.
+type FakeResponse = Result<(u16, &'static str), std::io::ErrorKind>;
+
+/// Answers the requests with the given responses, and records the requests.
+#[derive(Clone, Default)]
+struct FakeTransport {
+    responses: Arc<Mutex<Vec<FakeResponse>>>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl Transport for FakeTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, CanvasError>> {
+        Box::pin(async move {
+            self.requests.lock().unwrap().push(request.clone());
+
+            let (status, body) = self
+                .responses
+                .lock()
+                .unwrap()
+                .remove(0)
+                .map_err(std::io::Error::from)?;
+
+            Ok(HttpResponse {
+                status: reqwest::StatusCode::from_u16(status).unwrap(),
+                headers: Default::default(),
+                url: request.url,
+                body: body.into(),
+            })
+        })
+    }
+}
+
+fn canvas(transport: &FakeTransport) -> CanvasInformation {
+    CanvasInformation::builder("https://canvas.test.be", "MY_TOKEN")
+        .transport(transport.clone())
+        .retry_policy(RetryPolicy {
+            initial_backoff: std::time::Duration::ZERO,
+            ..RetryPolicy::default()
+        })
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn requests_are_sent_with_the_transport() {
+    let transport = FakeTransport::default();
+    transport
+        .responses
+        .lock()
+        .unwrap()
+        .push(Ok((200, r#"{"id": 1, "name": "Rust"}"#)));
+
+    let course = Canvas::get_course(1)
+        .unwrap()
+        .fetch(&canvas(&transport))
+        .await
+        .unwrap()
+        .inner();
+    assert_eq!(course.id, 1);
+
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].url.as_str(),
+        "https://canvas.test.be/api/v1/courses/1"
+    );
+    assert_eq!(
+        requests[0].headers[reqwest::header::AUTHORIZATION],
+        "Bearer MY_TOKEN"
+    );
+}
+
+#[tokio::test]
+async fn transport_errors_are_retried() {
+    let transport = FakeTransport::default();
+    transport.responses.lock().unwrap().extend([
+        Err(std::io::ErrorKind::ConnectionReset),
+        Ok((404, r#"{"errors": [{"message": "not found"}]}"#)),
+    ]);
+
+    let error = Canvas::get_course(1)
+        .unwrap()
+        .fetch(&canvas(&transport))
+        .await
+        .err()
+        .unwrap();
+
+    assert!(matches!(error, CanvasError::NotFound(_)));
+    assert_eq!(transport.requests.lock().unwrap().len(), 2);
+}
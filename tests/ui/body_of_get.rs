@@ -0,0 +1,10 @@
+use canvasapi::endpoint::canvas_endpoint;
+
+#[canvas_endpoint(GET, "courses/{course_id}", output = u64)]
+struct GetCourse {
+    course_id: u64,
+    #[body]
+    name: String,
+}
+
+fn main() {}
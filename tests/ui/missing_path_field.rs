@@ -0,0 +1,8 @@
+use canvasapi::endpoint::canvas_endpoint;
+
+#[canvas_endpoint(GET, "courses/{course_id}/users/{user_id}", output = u64)]
+struct GetUser {
+    course_id: u64,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use canvasapi::endpoint::canvas_endpoint;
+
+#[canvas_endpoint(GET, "courses/{course_id}", output = u64, pages)]
+struct GetCourse {
+    course_id: u64,
+}
+
+fn main() {}
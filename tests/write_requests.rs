@@ -0,0 +1,129 @@
+extern crate canvasapi;
+use canvasapi::prelude::*;
+
+mod common;
+use common::{FakeCanvas, Response};
+
+fn course() -> Course {
+    serde_json::from_str(r#"{"id": 1}"#).unwrap()
+}
+
+#[tokio::test]
+async fn post_with_body() {
+    let server = FakeCanvas::start();
+    server.on(
+        "POST",
+        "/api/v1/courses/1/assignments",
+        Response::json(r#"{"id": 11, "name": "Lab 1", "course_id": 1}"#),
+    );
+
+    let assignment = course()
+        .create_assignment()
+        .unwrap()
+        .body(serde_json::json!({ "assignment": { "name": "Lab 1", "points_possible": 10 } }))
+        .unwrap()
+        .fetch(&server.canvas())
+        .await
+        .unwrap()
+        .inner();
+    assert_eq!(assignment.id, Some(AssignmentId(11)));
+    assert_eq!(assignment.name.as_deref(), Some("Lab 1"));
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.path, "/api/v1/courses/1/assignments");
+    assert_eq!(request.header("content-type"), Some("application/json"));
+    let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({ "assignment": { "name": "Lab 1", "points_possible": 10 } })
+    );
+}
+
+#[tokio::test]
+async fn post_without_body() {
+    let server = FakeCanvas::start();
+    server.on(
+        "POST",
+        "/api/v1/courses/1/assignment_groups",
+        Response::json(r#"{"id": 3, "name": "Labs"}"#),
+    );
+
+    let group = course()
+        .create_assignment_group()
+        .unwrap()
+        .fetch(&server.canvas())
+        .await
+        .unwrap()
+        .inner();
+    assert_eq!(group.id, AssignmentGroupId(3));
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "POST");
+    assert!(request.body.is_empty());
+    assert_eq!(request.header("content-type"), None);
+}
+
+#[tokio::test]
+async fn delete_with_query() {
+    let server = FakeCanvas::start();
+    server.on(
+        "DELETE",
+        "/api/v1/courses/1?event=conclude",
+        Response::json(r#"{"conclude": true}"#),
+    );
+
+    let response = course()
+        .conclude()
+        .unwrap()
+        .fetch(&server.canvas())
+        .await
+        .unwrap()
+        .inner();
+    assert_eq!(response["conclude"], true);
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "DELETE");
+    assert_eq!(request.path, "/api/v1/courses/1");
+    assert_eq!(request.query_values("event"), ["conclude"]);
+    assert!(request.body.is_empty());
+}
+
+#[tokio::test]
+async fn delete_file() {
+    let server = FakeCanvas::start();
+    server.on(
+        "DELETE",
+        "/api/v1/files/7",
+        Response::json(r#"{"id": 7, "display_name": "notes.pdf"}"#),
+    );
+    let file: File = serde_json::from_str(r#"{"id": 7}"#).unwrap();
+
+    let deleted = file
+        .delete()
+        .unwrap()
+        .fetch(&server.canvas())
+        .await
+        .unwrap()
+        .inner();
+    assert_eq!(deleted.id, FileId(7));
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "DELETE");
+    assert_eq!(request.path, "/api/v1/files/7");
+}
+
+#[tokio::test]
+async fn failed_write_request() {
+    let server = FakeCanvas::start();
+    server.on(
+        "DELETE",
+        "/api/v1/files/7",
+        Response::error(403, "user not authorized to perform that action"),
+    );
+    let file: File = serde_json::from_str(r#"{"id": 7}"#).unwrap();
+
+    let result = file.delete().unwrap().fetch(&server.canvas()).await;
+    assert!(matches!(result, Err(CanvasError::Forbidden(_))));
+    assert_eq!(server.requests().len(), 1);
+}